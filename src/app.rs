@@ -1,29 +1,190 @@
-use crate::game::{evaluate_performance, ActionResult, GameAction, GamePhase, GameState};
+use crate::input::{evaluate, parse_works, Keywords, LineEditor, OVERFLOW};
 use crate::messages::MessageTemplates;
+use crate::planner::Planner;
+use crate::replay::{Playback, Recorder, AUTO_PLAY_DELAY};
+use crate::save::{default_save_path, SaveFile, SAVE_VERSION};
+use crate::theme::Theme;
 use anyhow::Result;
+use hammurabi::game::{
+    classic, GameAction, GamePhase, GameState, Optimal, Rejection, Replay, Rules, Strategy,
+    YearDecisions, YearEvent,
+};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::path::PathBuf;
 use std::time::Instant;
 
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EventMessage {
+    /// The event the text tells of, if any.
+    pub event: Option<YearEvent>,
+    pub text: String,
+}
+
+/// A decision taken this year that can still be taken back. Nothing reaches
+/// the recorder until the year is resolved.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DraftStep {
+    /// The state before the decision, restored when it is taken back.
+    pub before: GameState,
+    /// `None` when the land was left alone.
+    pub action: Option<GameAction>,
+    pub input: String,
+}
+
+/// An answer that can be given with a click.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    MaxBuy,
+    KeepLand,
+    MaxPlant,
+    FullRation,
+    /// Enter, for rulers who would rather not reach for the keyboard.
+    Decree,
+}
+
+/// When to stop the ruler before people are left to starve.
+#[derive(Debug, Clone)]
+pub struct FeedingWarnings {
+    /// Warn when more than this share of the people would starve, in
+    /// percent. Zero warns about any death; a famine that would impeach the
+    /// ruler is always warned about.
+    pub starvation_percent: u32,
+    /// Ask for a second Enter before a ration that was warned about.
+    pub confirm: bool,
+}
+
+impl Default for FeedingWarnings {
+    fn default() -> Self {
+        Self {
+            starvation_percent: 0,
+            confirm: true,
+        }
+    }
+}
+
 pub struct App {
     pub game: GameState,
-    pub input_buffer: String,
+    pub input: LineEditor,
     pub message: String,
-    pub event_messages: Vec<String>,
+    pub event_messages: Vec<EventMessage>,
+    pub draft: Vec<DraftStep>,
+    /// Set while the year is planned on a single screen.
+    pub planner: Option<Planner>,
+    pub final_report: Vec<String>,
     pub should_quit: bool,
     pub splash_start: Option<Instant>,
     pub messages: MessageTemplates,
+    pub save_path: PathBuf,
+    pub recorder: Recorder,
+    pub playback: Option<Playback>,
+    pub show_chronicle: bool,
+    pub chronicle_scroll: u16,
+    /// Lines of the year's events or final report scrolled past.
+    pub log_scroll: u16,
+    pub show_charts: bool,
+    pub show_help: bool,
+    pub help_scroll: u16,
+    pub advisor: Box<dyn Strategy>,
+    pub advice: Option<YearDecisions>,
+    pub autoplay: bool,
+    pub feeding_warnings: FeedingWarnings,
+    pub theme: Theme,
+    /// Bushels the ruler was warned about and may confirm with Enter.
+    pending_feed: Option<u32>,
+    last_autoplay_step: Instant,
 }
 
 impl App {
-    pub fn new(seed: Option<u64>, unlimited: bool) -> Self {
+    pub fn new(seed: Option<u64>, unlimited: bool, rules: Rules) -> Self {
+        // Always play from a known seed so the reign can be recorded
+        let seed = seed.unwrap_or_else(rand::random);
+
         Self {
-            game: GameState::new(seed, unlimited),
-            input_buffer: String::new(),
+            game: GameState::with_rules(Some(seed), unlimited, rules.clone()),
+            input: LineEditor::default(),
             message: String::new(),
             event_messages: Vec::new(),
+            draft: Vec::new(),
+            planner: None,
+            final_report: Vec::new(),
             should_quit: false,
             splash_start: Some(Instant::now()),
-            messages: MessageTemplates::new(seed),
+            messages: MessageTemplates::new(Some(seed)),
+            save_path: default_save_path(),
+            recorder: Recorder::new(seed, unlimited, rules),
+            playback: None,
+            show_chronicle: false,
+            chronicle_scroll: 0,
+            log_scroll: 0,
+            show_charts: false,
+            show_help: false,
+            help_scroll: 0,
+            advisor: Box::new(Optimal),
+            advice: None,
+            autoplay: false,
+            feeding_warnings: FeedingWarnings::default(),
+            theme: Theme::default(),
+            pending_feed: None,
+            last_autoplay_step: Instant::now(),
+        }
+    }
+
+    pub fn replay(replay: &Replay) -> Self {
+        let mut app = Self::new(Some(replay.seed), replay.unlimited, replay.rules.clone());
+        app.playback = Some(Playback::new(replay));
+        app
+    }
+
+    pub fn from_save(save: SaveFile, save_path: PathBuf) -> Self {
+        Self {
+            game: save.game,
+            input: LineEditor::default(),
+            message: String::new(),
+            event_messages: save.event_messages,
+            draft: save.draft,
+            planner: None,
+            final_report: Vec::new(),
+            should_quit: false,
+            splash_start: None,
+            messages: save.messages,
+            save_path,
+            recorder: save.recorder,
+            playback: None,
+            show_chronicle: false,
+            chronicle_scroll: 0,
+            log_scroll: 0,
+            show_charts: false,
+            show_help: false,
+            help_scroll: 0,
+            advisor: Box::new(Optimal),
+            advice: None,
+            autoplay: false,
+            feeding_warnings: FeedingWarnings::default(),
+            theme: Theme::default(),
+            pending_feed: None,
+            last_autoplay_step: Instant::now(),
+        }
+    }
+
+    pub fn save_game(&mut self) {
+        if self.game.current_phase == GamePhase::GameOver {
+            return;
         }
+
+        let save = SaveFile {
+            version: SAVE_VERSION,
+            game: self.game.clone(),
+            messages: self.messages.clone(),
+            event_messages: self.event_messages.clone(),
+            draft: self.draft.clone(),
+            recorder: self.recorder.clone(),
+        };
+
+        self.message = match save.write(&self.save_path) {
+            Ok(()) => format!("THY CHRONICLES ARE SEALED IN {}", self.save_path.display()),
+            Err(err) => format!("THE SCRIBES HAVE FAILED THEE: {}", err),
+        };
     }
 
     pub fn handle_input(&mut self, c: char) {
@@ -34,6 +195,19 @@ impl App {
             return;
         }
 
+        if self.playback.is_some() {
+            match c {
+                'a' => {
+                    if let Some(playback) = &mut self.playback {
+                        playback.auto = !playback.auto;
+                    }
+                }
+                ' ' => self.step_playback(),
+                _ => {}
+            }
+            return;
+        }
+
         if matches!(
             self.game.current_phase,
             GamePhase::Instructions | GamePhase::YearEnd | GamePhase::GameOver
@@ -41,14 +215,55 @@ impl App {
             return;
         }
 
-        match c {
-            '0'..='9' | '-' => {
-                self.input_buffer.push(c);
+        self.pending_feed = None;
+        match &mut self.planner {
+            Some(planner) if self.game.current_phase == GamePhase::LandTransaction => {
+                if c.is_ascii_digit() || c == '-' {
+                    planner.input().push(c);
+                }
+            }
+            _ => {
+                if c.is_ascii_alphanumeric() || "+-*/(), ".contains(c) {
+                    self.input.insert(c);
+                }
             }
-            _ => {}
         }
     }
 
+    /// The planner only has fields for the land, the sowing and the
+    /// feeding, so it is not offered when the year trades grain or builds.
+    pub fn planner_offered(&self) -> bool {
+        !self.game.rules.uses_silver() && !self.game.rules.builds()
+    }
+
+    pub fn planner_active(&self) -> bool {
+        self.planner.is_some() && self.game.current_phase == GamePhase::LandTransaction
+    }
+
+    /// Switches between planning the year on one screen and answering one
+    /// question at a time. Decisions already taken this year are undone.
+    pub fn toggle_planner(&mut self) {
+        if self.playback.is_some() || !self.game.current_phase.is_decision() {
+            return;
+        }
+
+        if self.planner.is_none() && !self.planner_offered() {
+            self.message = "THY YEAR HOLDS MORE THAN ONE SCREEN CAN PLAN".to_string();
+            return;
+        }
+
+        if let Some(first) = self.draft.drain(..).next() {
+            self.game = first.before;
+        }
+        self.input.clear();
+        self.message.clear();
+        self.pending_feed = None;
+        self.planner = match self.planner {
+            Some(_) => None,
+            None => Some(Planner::default()),
+        };
+    }
+
     pub fn handle_backspace(&mut self) {
         // Allow any key to skip splash
         if matches!(self.game.current_phase, GamePhase::Splash) {
@@ -57,28 +272,366 @@ impl App {
             return;
         }
 
-        self.input_buffer.pop();
+        self.pending_feed = None;
+        if let Some(planner) = self
+            .planner
+            .as_mut()
+            .filter(|_| self.game.current_phase == GamePhase::LandTransaction)
+        {
+            planner.input().pop();
+        } else if self.input.is_empty() {
+            self.step_back();
+        } else {
+            self.input.backspace();
+        }
+    }
+
+    pub fn handle_delete(&mut self) {
+        self.pending_feed = None;
+        self.input.delete();
+    }
+
+    pub fn handle_home(&mut self) {
+        self.input.home();
+    }
+
+    pub fn handle_end(&mut self) {
+        self.input.end();
+    }
+
+    /// Moves the cursor, or the focused field while planning.
+    pub fn handle_left_right(&mut self, delta: i32) {
+        if let Some(planner) = self
+            .planner
+            .as_mut()
+            .filter(|_| self.game.current_phase == GamePhase::LandTransaction)
+        {
+            planner.move_focus(delta);
+        } else if delta < 0 {
+            self.input.left();
+        } else {
+            self.input.right();
+        }
+    }
+
+    /// Recalls earlier answers while a decision is asked for, and scrolls
+    /// the chronicle otherwise.
+    pub fn handle_up_down(&mut self, delta: i32) {
+        if self.planner_active()
+            || self.playback.is_some()
+            || !self.game.current_phase.is_decision()
+        {
+            self.scroll(delta);
+            return;
+        }
+
+        self.pending_feed = None;
+        if delta < 0 {
+            self.input.recall_previous();
+        } else {
+            self.input.recall_next();
+        }
+    }
+
+    /// Takes back the last decision of the year, putting its answer back in
+    /// the input so it can be revised.
+    pub fn step_back(&mut self) {
+        if self.playback.is_some() || !self.game.current_phase.is_decision() {
+            return;
+        }
+
+        if let Some(step) = self.draft.pop() {
+            self.game = step.before;
+            self.input.set(step.input);
+            self.message.clear();
+        }
     }
 
     pub fn handle_enter(&mut self) -> Result<()> {
+        if self.playback.is_some() && self.game.current_phase != GamePhase::GameOver {
+            self.step_playback();
+            return Ok(());
+        }
+
+        self.submit();
+        Ok(())
+    }
+
+    fn submit(&mut self) {
         match self.game.current_phase {
             GamePhase::Splash => {
                 self.game.current_phase = GamePhase::Instructions;
                 self.splash_start = None;
             }
             GamePhase::Instructions => {
-                self.game.current_phase = GamePhase::LandTransaction;
+                self.game.current_phase = self.game.first_decision_phase();
             }
+            GamePhase::Taxation => self.process_taxation(),
+            GamePhase::LandTransaction if self.planner.is_some() => self.commit_plan(),
+            GamePhase::LandTransaction if self.trading_grain() => self.process_grain_trade(),
             GamePhase::LandTransaction => self.process_land_transaction(),
+            GamePhase::Construction => self.process_construction(),
             GamePhase::Planting => self.process_planting(),
             GamePhase::Feeding => self.process_feeding(),
             GamePhase::YearEnd => self.advance_to_next_year(),
-            GamePhase::GameOver => self.should_quit = true,
+            GamePhase::GameOver => {
+                if self.show_chronicle {
+                    self.should_quit = true;
+                } else {
+                    self.show_chronicle = true;
+                }
+            }
+        }
+    }
+
+    pub fn toggle_charts(&mut self) {
+        if self.game.current_phase.is_decision() || self.game.current_phase == GamePhase::YearEnd {
+            self.show_charts = !self.show_charts;
+        }
+    }
+
+    pub fn toggle_help(&mut self) {
+        self.show_help = !self.show_help;
+        self.help_scroll = 0;
+    }
+
+    pub fn scroll_help(&mut self, delta: i32) {
+        self.help_scroll = (self.help_scroll as i32 + delta).clamp(0, u16::MAX as i32) as u16;
+    }
+
+    /// The mouse wheel scrolls whatever is showing.
+    pub fn scroll_wheel(&mut self, delta: i32) {
+        if self.show_help {
+            self.scroll_help(delta);
+        } else if self.show_chronicle {
+            self.scroll(delta);
+        } else {
+            let lines = match self.game.current_phase {
+                GamePhase::YearEnd => self.event_messages.len(),
+                GamePhase::GameOver => self.final_report.len(),
+                _ => 0,
+            };
+            let max_scroll = lines.saturating_sub(1) as i32;
+            self.log_scroll = (self.log_scroll as i32 + delta).clamp(0, max_scroll) as u16;
+        }
+    }
+
+    /// The presets that can be clicked at the current decision.
+    pub fn presets(&self) -> Vec<Preset> {
+        if self.playback.is_some() || self.show_help || self.show_chronicle || self.show_charts {
+            return Vec::new();
+        }
+
+        match self.game.current_phase {
+            GamePhase::LandTransaction if self.planner.is_some() => vec![
+                Preset::MaxBuy,
+                Preset::MaxPlant,
+                Preset::FullRation,
+                Preset::Decree,
+            ],
+            GamePhase::Taxation => vec![Preset::Decree],
+            GamePhase::LandTransaction if self.trading_grain() => vec![Preset::Decree],
+            GamePhase::LandTransaction if self.selling_land() => {
+                vec![Preset::KeepLand, Preset::Decree]
+            }
+            GamePhase::LandTransaction => vec![Preset::MaxBuy, Preset::KeepLand, Preset::Decree],
+            GamePhase::Construction => vec![Preset::Decree],
+            GamePhase::Planting => vec![Preset::MaxPlant, Preset::Decree],
+            GamePhase::Feeding => vec![Preset::FullRation, Preset::Decree],
+            _ => Vec::new(),
+        }
+    }
+
+    /// A left click, on `preset` if it landed on one. Clicking anywhere
+    /// moves past the screens that only wait for Enter.
+    pub fn handle_click(&mut self, preset: Option<Preset>) -> Result<()> {
+        if self.show_help {
+            self.toggle_help();
+            return Ok(());
+        }
+
+        match self.game.current_phase {
+            GamePhase::Splash | GamePhase::Instructions | GamePhase::YearEnd => {
+                self.handle_enter()?;
+            }
+            _ => {
+                if let Some(preset) = preset {
+                    self.apply_preset(preset)?;
+                }
+            }
         }
         Ok(())
     }
 
-    pub fn check_splash_timeout(&mut self) {
+    fn apply_preset(&mut self, preset: Preset) -> Result<()> {
+        let game = &self.game;
+        let full_ration = game.grain_needed_for_feeding().min(game.grain);
+        let (field, amount) = match preset {
+            Preset::MaxBuy => (0, game.affordable_acres()),
+            Preset::KeepLand => (0, 0),
+            Preset::MaxPlant => {
+                let max_plant = match &self.planner {
+                    Some(planner) if self.planner_active() => {
+                        let decisions = planner.decisions().unwrap_or_default();
+                        game.project(&decisions).max_plantable_acres
+                    }
+                    _ => game.max_plantable_acres(),
+                };
+                (1, max_plant)
+            }
+            Preset::FullRation => (2, full_ration),
+            Preset::Decree => return self.handle_enter(),
+        };
+
+        self.pending_feed = None;
+        match &mut self.planner {
+            Some(planner) if self.game.current_phase == GamePhase::LandTransaction => {
+                planner.fields[field] = amount.to_string();
+                planner.focus = field;
+            }
+            _ => self.input.set(amount.to_string()),
+        }
+        Ok(())
+    }
+
+    pub fn scroll(&mut self, delta: i32) {
+        if !self.show_chronicle {
+            return;
+        }
+
+        let max_scroll = self.game.history.len().saturating_sub(1) as i32;
+        self.chronicle_scroll = (self.chronicle_scroll as i32 + delta).clamp(0, max_scroll) as u16;
+    }
+
+    fn step_playback(&mut self) {
+        let trading_grain = self.trading_grain();
+        let selling_land = self.selling_land();
+        let Some(playback) = &mut self.playback else {
+            return;
+        };
+
+        match playback.next_input(&self.game.current_phase, trading_grain, selling_land) {
+            Some(input) => {
+                self.input.set(input);
+                self.submit();
+            }
+            None => {
+                playback.auto = false;
+            }
+        }
+    }
+
+    pub fn show_advice(&mut self) {
+        if !self.game.current_phase.is_decision() {
+            return;
+        }
+
+        let advice = self.year_advice();
+        let tax = if self.game.rules.taxation.enabled {
+            format!("TAX {}%, ", advice.tax)
+        } else {
+            String::new()
+        };
+        let build = if self.game.rules.builds() {
+            format!("BUILD {}, ", advice.works())
+        } else {
+            String::new()
+        };
+        self.message = format!(
+            "THY ADVISOR ({}) COUNSELS: {}TRADE {} ACRES, {}PLANT {}, FEED {}",
+            self.advisor.name().to_uppercase(),
+            tax,
+            advice.land,
+            build,
+            advice.plant,
+            advice.feed
+        );
+        if self.planner_active() {
+            if let Some(planner) = &mut self.planner {
+                planner.fill(&advice);
+            }
+        } else if let Some(input) = self.advice_input() {
+            self.input.set(input);
+        }
+    }
+
+    pub fn toggle_autoplay(&mut self) {
+        if self.playback.is_none() {
+            self.autoplay = !self.autoplay;
+        }
+    }
+
+    /// The advisor's plan for the year, drawn up from the state at its start.
+    fn year_advice(&mut self) -> YearDecisions {
+        if let Some(advice) = &self.advice {
+            return advice.clone();
+        }
+
+        let advice = self.advisor.decide(&self.game);
+        self.advice = Some(advice.clone());
+        advice
+    }
+
+    /// What the advisor would type at the current phase. Later phases are
+    /// clamped in case the ruler strayed from the plan earlier in the year.
+    fn advice_input(&mut self) -> Option<String> {
+        match self.game.current_phase {
+            GamePhase::Splash | GamePhase::Instructions | GamePhase::YearEnd => Some(String::new()),
+            GamePhase::Taxation => Some(self.year_advice().tax.to_string()),
+            GamePhase::LandTransaction if self.trading_grain() => {
+                Some(self.year_advice().grain.to_string())
+            }
+            GamePhase::LandTransaction if self.game.rules.is_classic() => {
+                let land = self.year_advice().land;
+                let acres = if self.selling_land() { -land } else { land };
+                Some(acres.max(0).to_string())
+            }
+            GamePhase::LandTransaction => Some(self.year_advice().land.to_string()),
+            GamePhase::Construction => Some(self.year_advice().works().to_string()),
+            GamePhase::Planting => {
+                let plant = self.year_advice().plant;
+                Some(plant.min(self.game.max_plantable_acres()).to_string())
+            }
+            GamePhase::Feeding => {
+                let feed = self.year_advice().feed;
+                Some(feed.min(self.game.grain).to_string())
+            }
+            GamePhase::GameOver => None,
+        }
+    }
+
+    fn step_autoplay(&mut self) {
+        self.last_autoplay_step = Instant::now();
+
+        if self.planner_active() {
+            let advice = self.year_advice();
+            if let Some(planner) = &mut self.planner {
+                planner.fill(&advice);
+            }
+            self.submit();
+            return;
+        }
+
+        match self.advice_input() {
+            Some(input) => {
+                self.input.set(input);
+                self.submit();
+            }
+            None => self.autoplay = false,
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.check_splash_timeout();
+
+        if self.playback.as_ref().is_some_and(Playback::is_due) {
+            self.step_playback();
+        } else if self.autoplay && self.last_autoplay_step.elapsed() >= AUTO_PLAY_DELAY {
+            self.step_autoplay();
+        }
+    }
+
+    fn check_splash_timeout(&mut self) {
         if let Some(start) = self.splash_start {
             if start.elapsed().as_secs() >= 5 && self.game.current_phase == GamePhase::Splash {
                 self.game.current_phase = GamePhase::Instructions;
@@ -87,166 +640,794 @@ impl App {
         }
     }
 
+    /// What the words of an answer stand for at the current decision.
+    pub fn keywords(&self) -> Keywords {
+        let game = &self.game;
+        let (max, all) = match game.current_phase {
+            GamePhase::Taxation => (game.rules.taxation.max_rate, game.rules.taxation.max_rate),
+            GamePhase::LandTransaction if self.trading_grain() => {
+                (game.grain_for_silver(game.silver), game.grain)
+            }
+            GamePhase::LandTransaction => (game.affordable_acres(), game.land),
+            GamePhase::Planting => (game.max_plantable_acres(), game.land),
+            _ => (game.grain, game.grain),
+        };
+
+        Keywords {
+            max: max as i64,
+            all: all as i64,
+            need: game.grain_needed_for_feeding() as i64,
+        }
+    }
+
+    /// The typed answer worked out against the current state, or `None`
+    /// with the reason in the message when it cannot be.
+    fn answer(&mut self) -> Option<i64> {
+        if self.input.as_str().trim().is_empty() {
+            return None;
+        }
+
+        match evaluate(self.input.as_str(), &self.keywords()) {
+            Ok(value) => {
+                self.input.remember();
+                Some(value)
+            }
+            Err(reason) => {
+                self.message = reason;
+                None
+            }
+        }
+    }
+
+    /// The typed answer as a count of acres or bushels.
+    fn answer_amount(&mut self) -> Option<u32> {
+        let value = self.answer()?;
+        match u32::try_from(value) {
+            Ok(amount) => Some(amount),
+            Err(_) if value < 0 && self.game.rules.is_classic() => {
+                self.resign();
+                None
+            }
+            Err(_) if value < 0 => {
+                self.message = "THOU CANST NOT GIVE LESS THAN NOTHING".to_string();
+                None
+            }
+            Err(_) => {
+                self.message = OVERFLOW.to_string();
+                None
+            }
+        }
+    }
+
+    /// The listing's answer to a negative number: the steward walks out
+    /// and the reign is over.
+    pub fn resign(&mut self) {
+        self.final_report = classic::resignation();
+        self.game.current_phase = GamePhase::GameOver;
+    }
+
     fn process_land_transaction(&mut self) {
-        if let Ok(amount) = self.input_buffer.trim().parse::<i32>() {
-            let action = if amount > 0 {
-                GameAction::BuyLand(amount as u32)
-            } else if amount < 0 {
-                GameAction::SellLand((-amount) as u32)
-            } else {
-                self.game.current_phase = GamePhase::Planting;
-                self.input_buffer.clear();
-                return;
+        if self.input.as_str().trim().is_empty() {
+            self.keep_land();
+            return;
+        }
+        if self.game.rules.is_classic() {
+            // The listing asks to buy and then to sell, and neither takes a
+            // negative number
+            match self.answer_amount() {
+                Some(0) => self.keep_land(),
+                Some(acres) if self.selling_land() => self.take_action(GameAction::SellLand(acres)),
+                Some(acres) => self.take_action(GameAction::BuyLand(acres)),
+                None => {}
+            }
+            return;
+        }
+        let Some(amount) = self.answer() else {
+            return;
+        };
+
+        let Ok(acres) = u32::try_from(amount.unsigned_abs()) else {
+            self.message = OVERFLOW.to_string();
+            return;
+        };
+        match amount.cmp(&0) {
+            Ordering::Greater => self.take_action(GameAction::BuyLand(acres)),
+            Ordering::Less => self.take_action(GameAction::SellLand(acres)),
+            Ordering::Equal => self.keep_land(),
+        }
+    }
+
+    /// Whether the land has been traded and the silver economy now asks
+    /// for the year's dealings in grain.
+    pub fn trading_grain(&self) -> bool {
+        let land_steps = self
+            .draft
+            .iter()
+            .filter(|step| step.before.current_phase == GamePhase::LandTransaction)
+            .count();
+        self.game.rules.uses_silver()
+            && self.game.current_phase == GamePhase::LandTransaction
+            && land_steps == 1
+    }
+
+    /// Whether the classic steward, told to buy nothing, now asks how much
+    /// land to sell. The planner trades land in a single field instead.
+    pub fn selling_land(&self) -> bool {
+        let mut land_steps = self
+            .draft
+            .iter()
+            .filter(|step| step.before.current_phase == GamePhase::LandTransaction);
+        self.game.rules.is_classic()
+            && self.planner.is_none()
+            && self.game.current_phase == GamePhase::LandTransaction
+            && land_steps.next().is_some_and(|step| step.action.is_none())
+            && land_steps.next().is_none()
+    }
+
+    /// Levies the typed rate, or last year's when nothing is typed.
+    fn process_taxation(&mut self) {
+        if self.input.as_str().trim().is_empty() {
+            self.take_action(GameAction::SetTaxRate(self.game.tax_rate));
+            return;
+        }
+        if let Some(rate) = self.answer_amount() {
+            self.take_action(GameAction::SetTaxRate(rate));
+        }
+    }
+
+    fn process_grain_trade(&mut self) {
+        if self.input.as_str().trim().is_empty() {
+            self.keep_land();
+            return;
+        }
+        let Some(amount) = self.answer() else {
+            return;
+        };
+
+        let Ok(bushels) = u32::try_from(amount.unsigned_abs()) else {
+            self.message = OVERFLOW.to_string();
+            return;
+        };
+        match amount.cmp(&0) {
+            Ordering::Greater => self.take_action(GameAction::BuyGrain(bushels)),
+            Ordering::Less => self.take_action(GameAction::SellGrain(bushels)),
+            Ordering::Equal => self.keep_land(),
+        }
+    }
+
+    /// Takes the whole planned year at once, or nothing if any decision
+    /// cannot be carried out.
+    fn commit_plan(&mut self) {
+        let Some(planner) = &self.planner else {
+            return;
+        };
+        let decisions = planner.decisions();
+        let [land, plant, feed] = planner.fields.clone();
+        let Some(decisions) = decisions else {
+            self.message = "THY DECREES MUST BE WHOLE NUMBERS".to_string();
+            return;
+        };
+
+        if !self.planner_offered() {
+            self.message = "THY YEAR HOLDS MORE THAN ONE SCREEN CAN PLAN".to_string();
+            return;
+        }
+        if let Some((action, reason)) = self.game.project(&decisions).rejection {
+            self.message = self.rejection_message(&action, &reason);
+            return;
+        }
+        if !self.confirm_feeding(decisions.feed) {
+            return;
+        }
+
+        if decisions.land == 0 {
+            self.input.set(land.clone());
+            self.keep_land();
+        }
+        for action in decisions.actions(&self.game.rules) {
+            let input = match action {
+                // The year's tax was levied before the plan was drawn up, and
+                // years that trade grain or build were refused above
+                GameAction::SetTaxRate(_)
+                | GameAction::BuyGrain(_)
+                | GameAction::SellGrain(_)
+                | GameAction::Build(_) => continue,
+                GameAction::BuyLand(_) | GameAction::SellLand(_) => &land,
+                GameAction::PlantAcres(_) => &plant,
+                GameAction::FeedPopulation(_) => &feed,
             };
+            self.input.set(input.clone());
+            self.take_action(action);
+        }
+    }
 
-            match self.game.execute_action(action) {
-                ActionResult::Success => {
-                    self.game.current_phase = GamePhase::Planting;
-                    self.input_buffer.clear();
-                    self.message.clear();
-                }
-                ActionResult::InsufficientGrain => {
-                    self.message = self.messages.insufficient_grain_land_message();
-                }
-                ActionResult::InsufficientLand => {
-                    self.message = self.messages.insufficient_land_message();
-                }
-                ActionResult::InsufficientPopulation => {
-                    // This should never happen for land transactions, but handle it to be exhaustive
-                    unreachable!()
+    /// Leaves the land, or under the silver economy the grain, as it is.
+    fn keep_land(&mut self) {
+        self.draft.push(DraftStep {
+            before: self.game.clone(),
+            action: None,
+            input: self.input.as_str().to_string(),
+        });
+        self.next_phase();
+    }
+
+    /// Begins the ordered buildings, or none when nothing is typed.
+    fn process_construction(&mut self) {
+        match parse_works(self.input.as_str(), &self.game.rules.buildable()) {
+            Ok(works) => {
+                if !works.is_empty() {
+                    self.input.remember();
                 }
+                self.take_action(GameAction::Build(works));
             }
-        } else if self.input_buffer.is_empty() {
-            self.game.current_phase = GamePhase::Planting;
+            Err(reason) => self.message = reason,
         }
     }
 
     fn process_planting(&mut self) {
-        if let Ok(acres) = self.input_buffer.trim().parse::<u32>() {
-            match self.game.execute_action(GameAction::PlantAcres(acres)) {
-                ActionResult::Success => {
-                    self.game.current_phase = GamePhase::Feeding;
-                    self.input_buffer.clear();
-                    self.message.clear();
-                }
-                ActionResult::InsufficientGrain => {
-                    self.message = self.messages.insufficient_grain_seed_message();
-                }
-                ActionResult::InsufficientLand => {
-                    self.message = self.messages.insufficient_land_planting_message();
-                }
-                ActionResult::InsufficientPopulation => {
-                    self.message = self.messages.insufficient_workers_message();
-                }
-            }
+        if let Some(acres) = self.answer_amount() {
+            self.take_action(GameAction::PlantAcres(acres));
         }
     }
 
     fn process_feeding(&mut self) {
-        if let Ok(bushels) = self.input_buffer.trim().parse::<u32>() {
-            match self
-                .game
-                .execute_action(GameAction::FeedPopulation(bushels))
-            {
-                ActionResult::Success => {
-                    self.process_year_end();
-                    self.input_buffer.clear();
-                    self.message.clear();
-                }
-                ActionResult::InsufficientGrain => {
-                    self.message = self.messages.insufficient_grain_feeding_message();
-                }
-                ActionResult::InsufficientLand | ActionResult::InsufficientPopulation => {
-                    // These should never happen for feeding, but handle them to be exhaustive
-                    unreachable!()
-                }
+        if let Some(bushels) = self.answer_amount() {
+            if self.confirm_feeding(bushels) {
+                self.take_action(GameAction::FeedPopulation(bushels));
             }
         }
     }
 
-    fn process_year_end(&mut self) {
-        // Process random events
-        let outcomes = self.game.process_year_events(&mut self.messages);
-        self.event_messages.clear();
+    /// What the ruler should be told before handing out `bushels`, if the
+    /// ration would leave people to starve.
+    pub fn feeding_warning(&self, bushels: u32) -> Option<String> {
+        let population = self.game.population;
+        let deaths = self.game.starvation_for(bushels);
+        if self.game.rules.impeaches(deaths, population) {
+            return Some(format!(
+                "{} OF THY {} SUBJECTS WOULD STARVE AND THOU WOULDST BE CAST FROM THY THRONE!",
+                deaths, population
+            ));
+        }
 
-        // Add starvation report first if any
-        if self.game.deaths_starvation > 0 {
-            self.event_messages.push(
-                self.messages
-                    .starvation_message(self.game.deaths_starvation),
-            );
+        if deaths > 0 && deaths * 100 > self.feeding_warnings.starvation_percent * population {
+            Some(format!(
+                "{} OF THY {} SUBJECTS WOULD STARVE!",
+                deaths, population
+            ))
+        } else {
+            None
         }
+    }
 
-        for message in outcomes {
-            self.event_messages.push(message);
+    /// Whether `bushels` may be handed out. A warned ration needs Enter a
+    /// second time; the advisor, replays and the listing are never asked.
+    fn confirm_feeding(&mut self, bushels: u32) -> bool {
+        if !self.feeding_warnings.confirm
+            || self.autoplay
+            || self.playback.is_some()
+            || self.game.rules.is_classic()
+        {
+            return true;
         }
+        let Some(warning) = self.feeding_warning(bushels) else {
+            return true;
+        };
 
-        // Save year summary
-        self.game.save_year_summary();
+        if self.pending_feed.take() == Some(bushels) {
+            return true;
+        }
+        self.pending_feed = Some(bushels);
+        self.message = format!("BEWARE! {} PRESS ENTER AGAIN TO CONFIRM", warning);
+        false
+    }
 
-        // Check game over conditions
-        if self.game.is_game_over() {
-            self.game.current_phase = GamePhase::GameOver;
-            self.calculate_final_score();
+    fn take_action(&mut self, action: GameAction) {
+        let before = self.game.clone();
+        match self.game.execute_action(action.clone()).rejection() {
+            None => {
+                self.draft.push(DraftStep {
+                    before,
+                    action: Some(action),
+                    input: self.input.as_str().to_string(),
+                });
+                self.next_phase();
+            }
+            Some(reason) => self.message = self.rejection_message(&action, &reason),
+        }
+    }
+
+    /// Moves on to the next decision, or resolves the year after the last.
+    fn next_phase(&mut self) {
+        self.input.clear();
+        self.message.clear();
+
+        if self.trading_grain() || self.selling_land() {
+            return;
+        }
+        match self.game.next_decision_phase() {
+            Some(phase) => self.game.current_phase = phase,
+            None => self.process_year_end(),
+        }
+    }
+
+    fn rejection_message(&mut self, action: &GameAction, reason: &Rejection) -> String {
+        if self.game.rules.is_classic() {
+            return match reason {
+                Rejection::InsufficientGrain => classic::think_again_grain(self.game.grain)
+                    .concat()
+                    .trim_end()
+                    .to_string(),
+                Rejection::InsufficientLand => classic::think_again_land(self.game.land),
+                _ => classic::too_few_people(self.game.population),
+            };
+        }
+
+        match reason {
+            Rejection::InsufficientGrain => match action {
+                GameAction::PlantAcres(_) => self.messages.insufficient_grain_seed_message(),
+                GameAction::FeedPopulation(_) => self.messages.insufficient_grain_feeding_message(),
+                GameAction::Build(_) => self.messages.insufficient_grain_works_message(),
+                GameAction::SellGrain(_) => self.messages.insufficient_grain_sale_message(),
+                _ => self.messages.insufficient_grain_land_message(),
+            },
+            Rejection::InsufficientLand => match action {
+                GameAction::PlantAcres(_) => self.messages.insufficient_land_planting_message(),
+                _ => self.messages.insufficient_land_message(),
+            },
+            Rejection::InsufficientPopulation => match action {
+                GameAction::Build(_) => self.messages.insufficient_builders_message(),
+                _ => self.messages.insufficient_workers_message(),
+            },
+            Rejection::InsufficientSilver => self.messages.insufficient_silver_message(),
+            Rejection::MarketClosed => {
+                "NO MERCHANT IN THY REALM WILL TRADE GRAIN FOR SILVER!".to_string()
+            }
+            Rejection::TaxTooHigh => {
+                let max_rate = if self.game.rules.taxation.enabled {
+                    self.game.rules.taxation.max_rate
+                } else {
+                    0
+                };
+                format!("THY PEOPLE CANNOT BEAR MORE THAN {}% IN TAX!", max_rate)
+            }
+            Rejection::CannotBuild => {
+                "THY REALM KNOWS NOT HOW TO RAISE SUCH A BUILDING!".to_string()
+            }
+        }
+    }
+
+    fn process_year_end(&mut self) {
+        let report = self.game.resolve_year();
+        if report.game_over {
+            self.show_charts = false;
+        }
+        for step in self.draft.drain(..) {
+            if let Some(action) = step.action {
+                self.recorder.record(action);
+            }
+        }
+        self.recorder.finish_year();
+        self.event_messages = if self.game.rules.is_classic() {
+            // The steward reports in the listing's own words
+            classic::year_report(&self.game)
+                .into_iter()
+                .filter(|line| !line.is_empty())
+                .map(|text| EventMessage { event: None, text })
+                .collect()
         } else {
-            self.game.current_phase = GamePhase::YearEnd;
+            report
+                .events
+                .into_iter()
+                .map(|event| EventMessage {
+                    text: self.messages.event_message(&event),
+                    event: Some(event),
+                })
+                .collect()
+        };
+
+        if report.game_over {
+            self.calculate_final_score();
         }
     }
 
     fn advance_to_next_year(&mut self) {
         self.game.advance_year();
         self.event_messages.clear();
+        self.log_scroll = 0;
+        self.advice = None;
+        if self.planner.is_some() {
+            self.planner = Some(Planner::default());
+        }
     }
 
     fn calculate_final_score(&mut self) {
-        let score = evaluate_performance(
-            100,
-            self.game.population,
-            self.game.total_deaths,
-            self.game.land,
-        );
+        let score = self.game.final_score();
+        self.recorder.finish_reign(score.clone());
 
-        self.event_messages.clear();
+        self.final_report.clear();
 
-        if self.game.deaths_starvation > 0
-            && self.game.deaths_starvation * 100
-                / (self.game.population + self.game.deaths_starvation)
-                > 45
-        {
-            self.event_messages.push(
+        if self.game.rules.is_classic() {
+            self.final_report = if self.game.was_impeached() {
+                classic::impeachment(self.game.deaths_starvation)
+            } else {
+                // The last report runs straight into the summing up
+                self.event_messages.clear();
+                let mut lines = classic::year_report(&self.game);
+                lines.extend(classic::final_report(&mut self.game));
+                lines
+            };
+            return;
+        }
+
+        if self.game.was_impeached() {
+            self.final_report.push(
                 "THOU HAST STARVED MORE THAN HALF THY SUBJECTS IN A SINGLE YEAR!".to_string(),
             );
-            self.event_messages
+            self.final_report
                 .push("FOR THIS MOST GRIEVOUS SIN, THOU ART NOT ONLY".to_string());
-            self.event_messages
+            self.final_report
                 .push("CAST FROM THY THRONE, BUT SHALL BE REMEMBERED".to_string());
-            self.event_messages
+            self.final_report
                 .push("AS THE GREATEST FOOL TO EVER WEAR A CROWN!!!!".to_string());
+        } else if self.game.was_overthrown() {
+            self.final_report
+                .push("THY PEOPLE, CRUSHED BY THY TAXES, HAVE RISEN AGAINST THEE!".to_string());
+            self.final_report
+                .push("THE MOB STORMS THE PALACE AND DRAGS THEE FROM THY".to_string());
+            self.final_report
+                .push("THRONE. THY NAME SHALL BE A CURSE IN BABYLON!".to_string());
         } else {
-            if self.game.unlimited_mode {
-                self.event_messages.push(format!(
+            if self.game.unlimited_mode || self.game.rules.years != 10 {
+                self.final_report.push(format!(
                     "IN THY {}-YEAR REIGN OVER BABYLON:",
                     self.game.year - 1
                 ));
             } else {
-                self.event_messages
+                self.final_report
                     .push("IN THY TEN-YEAR REIGN OVER BABYLON:".to_string());
             }
-            self.event_messages.push(format!(
+            self.final_report.push(format!(
                 "{:.1} PERCENT OF THY SUBJECTS STARVED EACH YEAR",
                 score.death_rate / (self.game.year - 1) as f32
             ));
-            self.event_messages.push(format!(
+            self.final_report.push(format!(
                 "A TOTAL OF {} SOULS PERISHED UNDER THY RULE!",
                 score.total_deaths
             ));
-            self.event_messages.push(format!(
-                "THOU BEGAN WITH 10 ACRES PER SUBJECT AND ENDED WITH {:.1}",
+            let rules = &self.game.rules;
+            self.final_report.push(format!(
+                "THOU BEGAN WITH {} ACRES PER SUBJECT AND ENDED WITH {:.1}",
+                rules.starting_land / rules.starting_population,
                 score.acres_per_person
             ));
-            self.event_messages.push("".to_string());
-            self.event_messages
+            if self.game.rules.taxation.enabled {
+                self.final_report.push(format!(
+                    "THY PEOPLE'S UNREST AVERAGED {:.1} UNDER THY TAXES",
+                    score.mean_unrest
+                ));
+            }
+            self.final_report.push("".to_string());
+            self.final_report
                 .push(score.get_rating_message().to_string());
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(phase: GamePhase) -> App {
+        let mut app = App::new(Some(1), false, Rules::default());
+        app.game.current_phase = phase;
+        app
+    }
+
+    /// A hundred subjects waiting to be fed, with grain to spare.
+    fn feeding(rules: Rules, warnings: FeedingWarnings) -> App {
+        let mut app = App::new(Some(1), false, rules);
+        app.feeding_warnings = warnings;
+        app.game.current_phase = GamePhase::Feeding;
+        app.game.population = 100;
+        app.game.grain = 5000;
+        app
+    }
+
+    fn warning(app: &App, deaths: u32) -> Option<String> {
+        let bushels = (100 - deaths) * app.game.rules.bushels_per_person;
+        app.feeding_warning(bushels)
+    }
+
+    fn feed(app: &mut App, bushels: u32) {
+        answer(app, &bushels.to_string());
+    }
+
+    fn answer(app: &mut App, input: &str) {
+        app.input.set(input.to_string());
+        app.handle_enter().unwrap();
+    }
+
+    #[test]
+    fn the_charts_open_and_shut_during_the_year() {
+        for phase in [
+            GamePhase::LandTransaction,
+            GamePhase::Planting,
+            GamePhase::Feeding,
+            GamePhase::YearEnd,
+        ] {
+            let mut app = at(phase);
+            app.toggle_charts();
+            assert!(app.show_charts);
+            app.toggle_charts();
+            assert!(!app.show_charts);
+        }
+    }
+
+    #[test]
+    fn the_charts_stay_shut_outside_the_year() {
+        for phase in [
+            GamePhase::Splash,
+            GamePhase::Instructions,
+            GamePhase::GameOver,
+        ] {
+            let mut app = at(phase);
+            app.toggle_charts();
+            assert!(!app.show_charts);
+        }
+    }
+
+    #[test]
+    fn the_charts_stay_open_into_the_year_end() {
+        let mut app = at(GamePhase::Feeding);
+        app.toggle_charts();
+        feed(&mut app, 2000);
+        assert_eq!(app.game.current_phase, GamePhase::YearEnd);
+        assert!(app.show_charts);
+    }
+
+    #[test]
+    fn the_charts_close_when_the_reign_ends() {
+        let mut app = at(GamePhase::Feeding);
+        app.toggle_charts();
+        // Confirm the starvation warning
+        feed(&mut app, 0);
+        feed(&mut app, 0);
+        assert_eq!(app.game.current_phase, GamePhase::GameOver);
+        assert!(!app.show_charts);
+    }
+
+    #[test]
+    fn any_starvation_is_warned_about_by_default() {
+        let app = feeding(Rules::default(), FeedingWarnings::default());
+        assert_eq!(warning(&app, 0), None);
+        assert_eq!(
+            warning(&app, 1).as_deref(),
+            Some("1 OF THY 100 SUBJECTS WOULD STARVE!")
+        );
+    }
+
+    #[test]
+    fn starvation_within_the_threshold_passes_unwarned() {
+        let warnings = FeedingWarnings {
+            starvation_percent: 10,
+            confirm: true,
+        };
+        let app = feeding(Rules::default(), warnings);
+        assert_eq!(warning(&app, 10), None);
+        assert_eq!(
+            warning(&app, 11).as_deref(),
+            Some("11 OF THY 100 SUBJECTS WOULD STARVE!")
+        );
+    }
+
+    #[test]
+    fn impeachment_is_always_warned_about() {
+        let warnings = FeedingWarnings {
+            starvation_percent: 100,
+            confirm: true,
+        };
+        let app = feeding(Rules::default(), warnings);
+        let impeachment = app.game.rules.impeachment_percent;
+        assert_eq!(warning(&app, impeachment), None);
+        assert_eq!(
+            warning(&app, impeachment + 1).as_deref(),
+            Some("46 OF THY 100 SUBJECTS WOULD STARVE AND THOU WOULDST BE CAST FROM THY THRONE!")
+        );
+    }
+
+    #[test]
+    fn a_warned_ration_needs_a_second_enter() {
+        let mut app = feeding(Rules::default(), FeedingWarnings::default());
+        feed(&mut app, 1980);
+        assert_eq!(app.game.current_phase, GamePhase::Feeding);
+        assert!(app.message.starts_with("BEWARE! 1 OF THY 100"));
+
+        // A different ration is warned about afresh
+        feed(&mut app, 1960);
+        assert_eq!(app.game.current_phase, GamePhase::Feeding);
+        feed(&mut app, 1960);
+        assert_eq!(app.game.current_phase, GamePhase::YearEnd);
+    }
+
+    #[test]
+    fn rations_are_handed_out_at_once_without_confirmation() {
+        let warnings = FeedingWarnings {
+            starvation_percent: 0,
+            confirm: false,
+        };
+        let mut app = feeding(Rules::default(), warnings);
+        feed(&mut app, 1000);
+        assert_eq!(app.game.current_phase, GamePhase::GameOver);
+
+        // The listing never warns
+        let mut app = feeding(Rules::classic(), FeedingWarnings::default());
+        feed(&mut app, 1000);
+        assert_ne!(app.game.current_phase, GamePhase::Feeding);
+    }
+
+    #[test]
+    fn works_beyond_counting_cannot_be_built() {
+        let mut rules = Rules::default();
+        rules.granaries.enabled = true;
+        let mut app = App::new(Some(1), false, rules);
+        app.game.current_phase = GamePhase::Construction;
+        let grain = app.game.grain;
+
+        app.input.set("4000000000 granaries".to_string());
+        app.handle_enter().unwrap();
+        assert_eq!(app.game.current_phase, GamePhase::Construction);
+        assert_eq!(
+            app.message,
+            "THY REALM KNOWS NOT HOW TO RAISE SUCH A BUILDING!"
+        );
+        assert_eq!(app.game.grain, grain);
+    }
+
+    #[test]
+    fn the_classic_steward_asks_to_buy_then_to_sell() {
+        let mut app = at(GamePhase::LandTransaction);
+        app.game.rules = Rules::classic();
+        answer(&mut app, "0");
+        assert!(app.selling_land());
+        answer(&mut app, "10");
+        assert_eq!(app.game.land, 990);
+        assert_eq!(app.game.current_phase, GamePhase::Feeding);
+    }
+
+    #[test]
+    fn a_negative_classic_answer_resigns() {
+        for answers in [
+            &["-5"][..],
+            &["0", "-5"],
+            &["0", "0", "-2000"],
+            &["0", "0", "2000", "-1"],
+        ] {
+            let mut app = at(GamePhase::LandTransaction);
+            app.game.rules = Rules::classic();
+            for input in answers {
+                answer(&mut app, input);
+            }
+            assert_eq!(app.game.current_phase, GamePhase::GameOver, "{:?}", answers);
+            assert_eq!(app.final_report, classic::resignation());
+        }
+    }
+
+    #[test]
+    fn a_classic_sale_replays_at_the_sell_prompt() {
+        let mut replay = Replay::new(3, false, Rules::classic());
+        replay.years.push(vec![
+            GameAction::SellLand(18),
+            GameAction::FeedPopulation(2000),
+            GameAction::PlantAcres(900),
+        ]);
+        let mut app = App::replay(&replay);
+        while app.game.current_phase != GamePhase::YearEnd {
+            app.handle_enter().unwrap();
+        }
+        assert_eq!(app.game.land, 982);
+    }
+
+    #[test]
+    fn the_classic_planner_trades_land_in_one_field() {
+        let mut app = at(GamePhase::LandTransaction);
+        app.game.rules = Rules::classic();
+        app.planner = Some(Planner::default());
+        app.planner.as_mut().unwrap().fields =
+            ["0".to_string(), "500".to_string(), "2000".to_string()];
+        app.handle_enter().unwrap();
+        assert_eq!(app.game.current_phase, GamePhase::YearEnd);
+    }
+
+    /// What undo must bring back: the kingdom and the typed answer.
+    fn undo_point(app: &App) -> (serde_json::Value, String) {
+        (
+            serde_json::to_value(&app.game).unwrap(),
+            app.input.as_str().to_string(),
+        )
+    }
+
+    #[test]
+    fn undo_restores_each_trade_and_its_answer() {
+        let mut rules = Rules::default();
+        rules.economy.enabled = true;
+        let mut app = App::new(Some(1), false, rules);
+        app.game.current_phase = GamePhase::LandTransaction;
+
+        // Nothing to take back at the first step
+        let start = undo_point(&app);
+        app.handle_backspace();
+        assert_eq!(undo_point(&app), start);
+
+        answer(&mut app, "10");
+        assert!(app.trading_grain());
+        let trading = undo_point(&app);
+        answer(&mut app, "100");
+        assert_eq!(app.game.current_phase, GamePhase::Planting);
+
+        app.handle_backspace();
+        assert_eq!(undo_point(&app), (trading.0, "100".to_string()));
+        assert!(app.trading_grain());
+        app.input.clear();
+        app.handle_backspace();
+        assert_eq!(undo_point(&app), (start.0, "10".to_string()));
+    }
+
+    #[test]
+    fn undo_restores_a_ration() {
+        let mut app = at(GamePhase::LandTransaction);
+        app.game.rules = Rules::classic();
+        answer(&mut app, "0");
+        answer(&mut app, "0");
+        assert_eq!(app.game.current_phase, GamePhase::Feeding);
+        let feeding = undo_point(&app);
+        feed(&mut app, 2000);
+        assert_eq!(app.game.current_phase, GamePhase::Planting);
+
+        app.handle_backspace();
+        assert_eq!(undo_point(&app), (feeding.0, "2000".to_string()));
+    }
+
+    #[test]
+    fn a_plan_is_refused_when_the_year_trades_grain() {
+        let mut rules = Rules::default();
+        rules.economy.enabled = true;
+        let mut app = App::new(Some(1), false, rules);
+        app.game.current_phase = GamePhase::LandTransaction;
+        app.planner = Some(Planner::default());
+        app.planner.as_mut().unwrap().fields =
+            ["0".to_string(), "500".to_string(), "2000".to_string()];
+        let before = serde_json::to_value(&app.game).unwrap();
+
+        app.handle_enter().unwrap();
+        assert_eq!(app.message, "THY YEAR HOLDS MORE THAN ONE SCREEN CAN PLAN");
+        assert_eq!(serde_json::to_value(&app.game).unwrap(), before);
+    }
+
+    #[test]
+    fn the_wheel_scrolls_the_report_within_its_length() {
+        let mut app = at(GamePhase::Feeding);
+        feed(&mut app, 2000);
+        assert_eq!(app.game.current_phase, GamePhase::YearEnd);
+        let last = app.event_messages.len() as u16 - 1;
+
+        app.scroll_wheel(-1);
+        assert_eq!(app.log_scroll, 0);
+        app.scroll_wheel(1);
+        assert_eq!(app.log_scroll, 1);
+        app.scroll_wheel(100);
+        assert_eq!(app.log_scroll, last);
+    }
+
+    #[test]
+    fn the_wheel_scrolls_the_help_over_the_report() {
+        let mut app = at(GamePhase::Feeding);
+        feed(&mut app, 2000);
+        app.toggle_help();
+        app.scroll_wheel(-1);
+        assert_eq!(app.help_scroll, 0);
+        app.scroll_wheel(3);
+        assert_eq!(app.help_scroll, 3);
+        assert_eq!(app.log_scroll, 0);
+    }
+}
@@ -0,0 +1,34 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Preferences kept between reigns. Command line options take precedence.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Name of the colour theme.
+    pub theme: Option<String>,
+}
+
+impl Config {
+    /// The config at `path`, or the defaults when there is none.
+    pub fn read(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read config {}", path.display()))?;
+        toml::from_str(&contents)
+            .with_context(|| format!("failed to parse config {}", path.display()))
+    }
+}
+
+/// `$XDG_CONFIG_HOME/hammurabi/config.toml`, or the platform equivalent.
+pub fn default_config_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("hammurabi")
+        .join("config.toml")
+}
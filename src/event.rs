@@ -1,9 +1,11 @@
-use crossterm::event::{self, Event as CEvent, KeyEvent};
+use crossterm::event::{self, Event as CEvent, KeyEvent, MouseEvent};
 use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub enum Event {
     Key(KeyEvent),
+    Mouse(MouseEvent),
+    Resize(u16, u16),
     Tick,
 }
 
@@ -18,6 +20,8 @@ impl EventHandler {
         if event::poll(Duration::from_millis(100))? {
             match event::read()? {
                 CEvent::Key(key) => Ok(Event::Key(key)),
+                CEvent::Mouse(mouse) => Ok(Event::Mouse(mouse)),
+                CEvent::Resize(width, height) => Ok(Event::Resize(width, height)),
                 _ => Ok(Event::Tick),
             }
         } else {
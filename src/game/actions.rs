@@ -1,19 +1,63 @@
+use crate::game::construction::Works;
 use crate::game::state::GameState;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GameAction {
     BuyLand(u32),
     SellLand(u32),
     PlantAcres(u32),
     FeedPopulation(u32),
+    /// Bushels bought with silver, under the silver economy only.
+    BuyGrain(u32),
+    SellGrain(u32),
+    /// The year's tax, in percent, when the rules levy taxes.
+    SetTaxRate(u32),
+    /// Buildings to begin this year, when the rules allow building.
+    Build(Works),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ActionResult {
     Success,
     InsufficientGrain,
     InsufficientLand,
     InsufficientPopulation,
+    InsufficientSilver,
+    /// Grain was traded without the silver economy.
+    MarketClosed,
+    /// The rate exceeds what the rules allow, or taxes are not levied.
+    TaxTooHigh,
+    /// A building the rules do not allow was ordered.
+    CannotBuild,
+}
+
+/// Why an action was refused: every [`ActionResult`] but success.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Rejection {
+    InsufficientGrain,
+    InsufficientLand,
+    InsufficientPopulation,
+    InsufficientSilver,
+    MarketClosed,
+    TaxTooHigh,
+    CannotBuild,
+}
+
+impl ActionResult {
+    /// The reason the action was refused, or `None` if it was carried out.
+    pub fn rejection(self) -> Option<Rejection> {
+        match self {
+            ActionResult::Success => None,
+            ActionResult::InsufficientGrain => Some(Rejection::InsufficientGrain),
+            ActionResult::InsufficientLand => Some(Rejection::InsufficientLand),
+            ActionResult::InsufficientPopulation => Some(Rejection::InsufficientPopulation),
+            ActionResult::InsufficientSilver => Some(Rejection::InsufficientSilver),
+            ActionResult::MarketClosed => Some(Rejection::MarketClosed),
+            ActionResult::TaxTooHigh => Some(Rejection::TaxTooHigh),
+            ActionResult::CannotBuild => Some(Rejection::CannotBuild),
+        }
+    }
 }
 
 impl GameState {
@@ -23,6 +67,10 @@ impl GameState {
             GameAction::SellLand(acres) => self.sell_land(acres),
             GameAction::PlantAcres(acres) => self.plant_acres(acres),
             GameAction::FeedPopulation(bushels) => self.feed_population(bushels),
+            GameAction::BuyGrain(bushels) => self.buy_grain(bushels),
+            GameAction::SellGrain(bushels) => self.sell_grain(bushels),
+            GameAction::SetTaxRate(rate) => self.set_tax_rate(rate),
+            GameAction::Build(works) => self.build(works),
         }
     }
 
@@ -31,13 +79,21 @@ impl GameState {
             return ActionResult::Success;
         }
 
-        let cost = acres * self.land_price;
-        if cost > self.grain {
-            return ActionResult::InsufficientGrain;
+        let cost = acres.checked_mul(self.land_price);
+        if self.rules.uses_silver() {
+            let Some(cost) = cost.filter(|&cost| cost <= self.silver) else {
+                return ActionResult::InsufficientSilver;
+            };
+            self.silver -= cost;
+        } else {
+            let Some(cost) = cost.filter(|&cost| cost <= self.grain) else {
+                return ActionResult::InsufficientGrain;
+            };
+            self.grain -= cost;
         }
 
-        self.grain -= cost;
         self.land += acres;
+        self.acres_bought += acres;
         ActionResult::Success
     }
 
@@ -46,12 +102,64 @@ impl GameState {
             return ActionResult::Success;
         }
 
-        if acres > self.land {
+        // The classic listing never lets the city sell its last acre
+        if acres > self.land || (self.rules.is_classic() && acres == self.land) {
             return ActionResult::InsufficientLand;
         }
 
+        let proceeds = acres.checked_mul(self.land_price);
+        let treasury = if self.rules.uses_silver() {
+            &mut self.silver
+        } else {
+            &mut self.grain
+        };
+        let Some(total) = proceeds.and_then(|proceeds| treasury.checked_add(proceeds)) else {
+            return ActionResult::InsufficientLand;
+        };
+
+        *treasury = total;
         self.land -= acres;
-        self.grain += acres * self.land_price;
+        self.acres_sold += acres;
+        ActionResult::Success
+    }
+
+    fn buy_grain(&mut self, bushels: u32) -> ActionResult {
+        if !self.rules.uses_silver() {
+            return ActionResult::MarketClosed;
+        }
+
+        let cost = self.grain_cost(bushels);
+        if cost > self.silver as u64 {
+            return ActionResult::InsufficientSilver;
+        }
+
+        let Some(grain) = self.grain.checked_add(bushels) else {
+            return ActionResult::InsufficientSilver;
+        };
+
+        self.silver -= cost as u32;
+        self.grain = grain;
+        self.grain_bought = self.grain_bought.saturating_add(bushels);
+        ActionResult::Success
+    }
+
+    fn sell_grain(&mut self, bushels: u32) -> ActionResult {
+        if !self.rules.uses_silver() {
+            return ActionResult::MarketClosed;
+        }
+
+        if bushels > self.grain {
+            return ActionResult::InsufficientGrain;
+        }
+
+        let proceeds = u32::try_from(self.grain_value(bushels)).ok();
+        let Some(silver) = proceeds.and_then(|proceeds| self.silver.checked_add(proceeds)) else {
+            return ActionResult::InsufficientGrain;
+        };
+
+        self.grain -= bushels;
+        self.silver = silver;
+        self.grain_sold = self.grain_sold.saturating_add(bushels);
         ActionResult::Success
     }
 
@@ -65,15 +173,16 @@ impl GameState {
             return ActionResult::InsufficientLand;
         }
 
-        if acres > self.grain {
+        let seed = self.rules.seed_for(acres);
+        if seed > self.grain {
             return ActionResult::InsufficientGrain;
         }
 
-        if acres > self.population * 10 {
+        if acres > self.workable_acres() {
             return ActionResult::InsufficientPopulation;
         }
 
-        self.grain -= acres;
+        self.grain -= seed;
         self.acres_planted = acres;
         ActionResult::Success
     }
@@ -84,8 +193,14 @@ impl GameState {
         }
 
         self.grain -= bushels;
+        self.grain_fed = bushels;
+
+        // Classic famine strikes only once the harvest is in
+        if self.rules.is_classic() {
+            return ActionResult::Success;
+        }
 
-        let people_fed = bushels / 20;
+        let people_fed = bushels / self.rules.bushels_per_person;
         if people_fed < self.population {
             self.deaths_starvation = self.population - people_fed;
             self.population = people_fed;
@@ -0,0 +1,301 @@
+//! The 1968/1973 BASIC HAMURABI, line for line. The arithmetic below runs
+//! when the rules select [`Ruleset::Classic`](crate::game::Ruleset), and the
+//! text functions print exactly what the listing prints, so a reign can be
+//! rendered as the teletype transcript it would have produced.
+
+use crate::game::events::YearEvent;
+use crate::game::scoring::PerformanceRating;
+use crate::game::state::GameState;
+use rand::Rng;
+
+pub const BUY_PROMPT: &str = "HOW MANY ACRES DO YOU WISH TO BUY";
+pub const SELL_PROMPT: &str = "HOW MANY ACRES DO YOU WISH TO SELL";
+pub const FEED_PROMPT: &str = "HOW MANY BUSHELS DO YOU WISH TO FEED YOUR PEOPLE";
+pub const PLANT_PROMPT: &str = "HOW MANY ACRES DO YOU WISH TO PLANT WITH SEED";
+
+const FINK: [&str; 3] = [
+    "DUE TO THIS EXTREME MISMANAGEMENT YOU HAVE NOT ONLY",
+    "BEEN IMPEACHED AND THROWN OUT OF OFFICE BUT YOU HAVE",
+    "ALSO BEEN DECLARED NATIONAL FINK!!!!",
+];
+
+impl GameState {
+    /// RND(1)
+    fn rnd(&mut self) -> f64 {
+        self.rng.gen()
+    }
+
+    /// C=INT(RND(1)*5)+1
+    fn roll(&mut self) -> u32 {
+        (self.rnd() * 5.0) as u32 + 1
+    }
+
+    /// Lines 511-555 of the listing. Immigration and plague are rolled here
+    /// but only applied by the next report, after the famine.
+    pub(crate) fn process_classic_year_events(&mut self) -> Vec<YearEvent> {
+        let mut events = Vec::new();
+
+        self.harvest_yield = self.roll();
+        self.grain_harvested = self.acres_planted * self.harvest_yield;
+        events.push(YearEvent::Harvest {
+            yield_per_acre: self.harvest_yield,
+            total: self.grain_harvested,
+        });
+
+        // Rats raid the stores before the harvest is brought in
+        let rats = self.roll();
+        self.grain_eaten_by_rats = 0;
+        if rats.is_multiple_of(2) {
+            self.grain_eaten_by_rats = self.grain / rats;
+            events.push(YearEvent::Rats {
+                eaten: self.grain_eaten_by_rats,
+                percent: 100 / rats,
+            });
+        }
+        self.grain = self.grain - self.grain_eaten_by_rats + self.grain_harvested;
+
+        let factor = self.roll() as u64;
+        let newcomers = factor * (20 * self.land as u64 + self.grain as u64)
+            / (100 * self.population.max(1) as u64)
+            + 1;
+
+        let people_fed = self.grain_fed / self.rules.bushels_per_person;
+        self.new_citizens = 0;
+        let plague = (10.0 * (2.0 * self.rnd() - 0.3)).floor() <= 0.0;
+
+        self.deaths_starvation = self.population.saturating_sub(people_fed);
+        if self.deaths_starvation > 0 {
+            let before = self.population;
+            self.population = people_fed;
+            self.total_deaths += self.deaths_starvation;
+            events.insert(
+                0,
+                YearEvent::Starvation {
+                    deaths: self.deaths_starvation,
+                },
+            );
+
+            if self.rules.impeaches(self.deaths_starvation, before) {
+                return events;
+            }
+        }
+
+        self.new_citizens = newcomers.min(self.rules.immigration_cap as u64) as u32;
+        self.population += self.new_citizens;
+        events.push(YearEvent::Immigration {
+            count: self.new_citizens,
+        });
+
+        if plague {
+            let survivors = self.population / 2;
+            self.deaths_plague = self.population - survivors;
+            self.population = survivors;
+            self.total_deaths += self.deaths_plague;
+            events.push(YearEvent::Plague {
+                deaths: self.deaths_plague,
+            });
+        }
+
+        events
+    }
+
+    /// P1, the yearly starvation percentage averaged the listing's way: it
+    /// skips every year whose grain could have fed more people than lived.
+    pub fn classic_starvation_average(&self) -> f64 {
+        self.history.iter().fold(0.0, |average, record| {
+            let people_fed = record.grain_fed / self.rules.bushels_per_person;
+            if record.start_population < people_fed {
+                return average;
+            }
+
+            let year = record.year as f64;
+            let percent = record.deaths_starvation as f64 * 100.0 / record.start_population as f64;
+            ((year - 1.0) * average + percent) / year
+        })
+    }
+
+    /// D1, everyone who starved during the reign.
+    pub fn classic_starvation_total(&self) -> u32 {
+        self.history
+            .iter()
+            .map(|record| record.deaths_starvation)
+            .sum()
+    }
+}
+
+/// A number as BASIC's PRINT writes it: a blank where the sign would go, at
+/// most six significant digits and a trailing blank.
+pub fn basic_number(value: f64) -> String {
+    let sign = if value < 0.0 { "-" } else { " " };
+    let value = value.abs();
+
+    let digits = if value == value.trunc() {
+        format!("{}", value as u64)
+    } else {
+        let whole_digits = value.log10().floor() as i32 + 1;
+        let decimals = (6 - whole_digits).max(0) as usize;
+        let fixed = format!("{:.*}", decimals, value);
+        let trimmed = fixed.trim_end_matches('0').trim_end_matches('.');
+        match trimmed.strip_prefix('0') {
+            Some("") => "0".to_string(),
+            Some(fraction) => fraction.to_string(),
+            None => trimmed.to_string(),
+        }
+    };
+
+    format!("{}{} ", sign, digits)
+}
+
+fn number(value: u32) -> String {
+    basic_number(value as f64)
+}
+
+/// Lines 10-90, up to the first report.
+pub fn opening() -> Vec<String> {
+    vec![
+        format!("{:32}HAMURABI", ""),
+        format!("{:15}CREATIVE COMPUTING  MORRISTOWN, NEW JERSEY", ""),
+        String::new(),
+        String::new(),
+        String::new(),
+        "TRY YOUR HAND AT GOVERNING ANCIENT SUMERIA".to_string(),
+        "FOR A TEN-YEAR TERM OF OFFICE.".to_string(),
+        String::new(),
+    ]
+}
+
+/// The steward's report on the year just resolved, or on the imaginary year
+/// before the reign when nothing has been played yet.
+pub fn year_report(state: &GameState) -> Vec<String> {
+    let year = state.history.last().map_or(1, |record| record.year + 1);
+
+    let mut lines = vec![
+        String::new(),
+        String::new(),
+        "HAMURABI:  I BEG TO REPORT TO YOU,".to_string(),
+        format!(
+            "IN YEAR{},{}PEOPLE STARVED,{}CAME TO THE CITY,",
+            number(year),
+            number(state.deaths_starvation),
+            number(state.new_citizens)
+        ),
+    ];
+    if state.deaths_plague > 0 {
+        lines.push("A HORRIBLE PLAGUE STRUCK!  HALF THE PEOPLE DIED.".to_string());
+    }
+    lines.extend([
+        format!("POPULATION IS NOW{}", number(state.population)),
+        format!("THE CITY NOW OWNS {}ACRES.", number(state.land)),
+        format!(
+            "YOU HARVESTED{}BUSHELS PER ACRE.",
+            number(state.harvest_yield)
+        ),
+        format!("THE RATS ATE{}BUSHELS.", number(state.grain_eaten_by_rats)),
+        format!("YOU NOW HAVE {}BUSHELS IN STORE.", number(state.grain)),
+        String::new(),
+    ]);
+    lines
+}
+
+pub fn land_price(price: u32) -> String {
+    format!("LAND IS TRADING AT{}BUSHELS PER ACRE.", number(price))
+}
+
+pub fn think_again_grain(grain: u32) -> Vec<String> {
+    vec![
+        "HAMURABI:  THINK AGAIN.  YOU HAVE ONLY".to_string(),
+        format!("{}BUSHELS OF GRAIN.  NOW THEN,", number(grain)),
+    ]
+}
+
+pub fn think_again_land(land: u32) -> String {
+    format!(
+        "HAMURABI:  THINK AGAIN.  YOU OWN ONLY{}ACRES.  NOW THEN,",
+        number(land)
+    )
+}
+
+pub fn too_few_people(population: u32) -> String {
+    format!(
+        "BUT YOU HAVE ONLY{}PEOPLE TO TEND THE FIELDS!  NOW THEN,",
+        number(population)
+    )
+}
+
+/// Lines 850-855, printed when the ruler answers with a negative number.
+pub fn resignation() -> Vec<String> {
+    vec![
+        String::new(),
+        "HAMURABI:  I CANNOT DO WHAT YOU WISH.".to_string(),
+        "GET YOURSELF ANOTHER STEWARD!!!!!".to_string(),
+    ]
+}
+
+pub fn impeachment(deaths: u32) -> Vec<String> {
+    let mut lines = vec![
+        String::new(),
+        format!("YOU STARVED{}PEOPLE IN ONE YEAR!!!", number(deaths)),
+    ];
+    lines.extend(FINK.map(String::from));
+    lines
+}
+
+/// Lines 860-975. A fair reign rolls RND once more to count the people
+/// who would like to see the ruler assassinated.
+pub fn final_report(state: &mut GameState) -> Vec<String> {
+    let score = state.final_score();
+
+    let mut lines = vec![
+        format!(
+            "IN YOUR 10-YEAR TERM OF OFFICE,{}PERCENT OF THE",
+            basic_number(state.classic_starvation_average())
+        ),
+        "POPULATION STARVED PER YEAR ON THE AVERAGE, I.E. A TOTAL OF".to_string(),
+        format!("{}PEOPLE DIED!!", number(state.classic_starvation_total())),
+        "YOU STARTED WITH 10 ACRES PER PERSON AND ENDED WITH".to_string(),
+        format!(
+            "{}ACRES PER PERSON.",
+            basic_number(state.land as f64 / state.population as f64)
+        ),
+        String::new(),
+    ];
+
+    match score.rating {
+        PerformanceRating::Terrible => lines.extend(FINK.map(String::from)),
+        PerformanceRating::Poor => lines.extend(
+            [
+                "YOUR HEAVY-HANDED PERFORMANCE SMACKS OF NERO AND IVAN IV.",
+                "THE PEOPLE (REMIANING) FIND YOU AN UNPLEASANT RULER, AND,",
+                "FRANKLY, HATE YOUR GUTS!!",
+            ]
+            .map(String::from),
+        ),
+        PerformanceRating::Fair | PerformanceRating::Good => {
+            let assassins = (state.population as f64 * 0.8 * state.rnd()) as u32;
+            lines.extend([
+                "YOUR PERFORMANCE COULD HAVE BEEN SOMEWHAT BETTER, BUT".to_string(),
+                format!("REALLY WASN'T TOO BAD AT ALL. {}PEOPLE", number(assassins)),
+                "WOULD DEARLY LIKE TO SEE YOU ASSASSINATED BUT WE ALL HAVE OUR".to_string(),
+                "TRIVIAL PROBLEMS.".to_string(),
+            ]);
+        }
+        PerformanceRating::Excellent => lines.extend(
+            [
+                "A FANTASTIC PERFORMANCE!!!  CHARLEMANGE, DISRAELI, AND",
+                "JEFFERSON COMBINED COULD NOT HAVE DONE BETTER!",
+            ]
+            .map(String::from),
+        ),
+    }
+
+    lines
+}
+
+/// Lines 990-995, ten bells and a goodbye.
+pub fn farewell() -> Vec<String> {
+    vec![
+        String::new(),
+        format!("{}SO LONG FOR NOW.", "\u{7}".repeat(10)),
+        String::new(),
+    ]
+}
@@ -0,0 +1,298 @@
+use std::fmt;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::game::actions::ActionResult;
+use crate::game::events::YearEvent;
+use crate::game::state::GameState;
+
+/// The kinds of works the people can build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Building {
+    Canal,
+    Wall,
+    Temple,
+    Granary,
+}
+
+impl Building {
+    pub const ALL: [Building; 4] = [
+        Building::Canal,
+        Building::Wall,
+        Building::Temple,
+        Building::Granary,
+    ];
+
+    /// The steward's name for `count` of them.
+    pub fn name(self, count: u32) -> &'static str {
+        match (self, count == 1) {
+            (Building::Canal, true) => "CANAL",
+            (Building::Canal, false) => "CANALS",
+            (Building::Wall, true) => "WALL",
+            (Building::Wall, false) => "WALLS",
+            (Building::Temple, true) => "TEMPLE",
+            (Building::Temple, false) => "TEMPLES",
+            (Building::Granary, true) => "GRANARY",
+            (Building::Granary, false) => "GRANARIES",
+        }
+    }
+}
+
+/// How many of each kind of building to begin in one year.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Works {
+    pub canals: u32,
+    pub walls: u32,
+    pub temples: u32,
+    pub granaries: u32,
+}
+
+impl Works {
+    pub fn count(&self, building: Building) -> u32 {
+        match building {
+            Building::Canal => self.canals,
+            Building::Wall => self.walls,
+            Building::Temple => self.temples,
+            Building::Granary => self.granaries,
+        }
+    }
+
+    pub fn count_mut(&mut self, building: Building) -> &mut u32 {
+        match building {
+            Building::Canal => &mut self.canals,
+            Building::Wall => &mut self.walls,
+            Building::Temple => &mut self.temples,
+            Building::Granary => &mut self.granaries,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// The kinds ordered at least once, with their counts.
+    pub fn iter(&self) -> impl Iterator<Item = (Building, u32)> + '_ {
+        Building::ALL
+            .into_iter()
+            .map(|building| (building, self.count(building)))
+            .filter(|&(_, count)| count > 0)
+    }
+}
+
+/// Reads as the ruler would order them, `2 GRANARIES, 1 WALL`, or
+/// `NOTHING`.
+impl fmt::Display for Works {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "NOTHING");
+        }
+        for (i, (building, count)) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} {}", count, building.name(count))?;
+        }
+        Ok(())
+    }
+}
+
+/// A building under way and the years of work it still needs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Project {
+    pub building: Building,
+    pub years_left: u32,
+}
+
+impl GameState {
+    /// Begins `works`, paying for them in grain at once and keeping their
+    /// builders from the fields until they stand.
+    pub(crate) fn build(&mut self, works: Works) -> ActionResult {
+        if works.is_empty() {
+            return ActionResult::Success;
+        }
+        if works
+            .iter()
+            .any(|(building, _)| !self.rules.can_build(building))
+        {
+            return ActionResult::CannotBuild;
+        }
+
+        let (Some(cost), Some(labor)) = (
+            self.rules.works_cost(&works),
+            self.rules.works_labor(&works),
+        ) else {
+            return ActionResult::CannotBuild;
+        };
+        if cost > self.grain {
+            return ActionResult::InsufficientGrain;
+        }
+        if self.builders().saturating_add(labor) > self.population {
+            return ActionResult::InsufficientPopulation;
+        }
+
+        self.grain -= cost;
+        for (building, count) in works.iter() {
+            let years = self.rules.project(building).years;
+            for _ in 0..count {
+                self.projects.push(Project {
+                    building,
+                    years_left: years,
+                });
+            }
+        }
+        ActionResult::Success
+    }
+
+    /// Buildings of the kind standing.
+    pub fn built(&self, building: Building) -> u32 {
+        match building {
+            Building::Canal => self.canals,
+            Building::Wall => self.walls,
+            Building::Temple => self.temples,
+            Building::Granary => self.granaries,
+        }
+    }
+
+    fn built_mut(&mut self, building: Building) -> &mut u32 {
+        match building {
+            Building::Canal => &mut self.canals,
+            Building::Wall => &mut self.walls,
+            Building::Temple => &mut self.temples,
+            Building::Granary => &mut self.granaries,
+        }
+    }
+
+    /// Buildings of each kind still under way.
+    pub fn works_under_way(&self) -> Works {
+        let mut works = Works::default();
+        for project in &self.projects {
+            *works.count_mut(project.building) += 1;
+        }
+        works
+    }
+
+    /// People kept from the fields by the works under way.
+    pub fn builders(&self) -> u32 {
+        self.rules
+            .works_labor(&self.works_under_way())
+            .unwrap_or(u32::MAX)
+    }
+
+    /// Most buildings of the kind the stores and the idle people can begin.
+    pub fn affordable(&self, building: Building) -> u32 {
+        if !self.rules.can_build(building) {
+            return 0;
+        }
+        let project = self.rules.project(building);
+        let by_grain = self.grain.checked_div(project.grain_cost);
+        let idle = self.population.saturating_sub(self.builders());
+        let by_people = idle.checked_div(project.workers);
+        by_grain
+            .unwrap_or(u32::MAX)
+            .min(by_people.unwrap_or(u32::MAX))
+    }
+
+    /// Bushels the standing buildings cost each year.
+    pub fn upkeep(&self) -> u32 {
+        Building::ALL
+            .into_iter()
+            .map(|building| self.built(building) * self.rules.project(building).upkeep)
+            .sum()
+    }
+
+    /// The poorest harvest an acre can yield with the canals dug so far.
+    pub fn harvest_floor(&self) -> u32 {
+        let rules = &self.rules;
+        if !rules.construction.enabled {
+            return rules.harvest_yield_min;
+        }
+        (rules.harvest_yield_min + self.canals * rules.construction.canal_yield)
+            .min(rules.harvest_yield_max)
+    }
+
+    /// Share of a raid's plunder the walls save, in percent.
+    pub fn wall_protection(&self) -> u32 {
+        let construction = &self.rules.construction;
+        if !construction.enabled {
+            return 0;
+        }
+        (self.walls * construction.wall_protection).min(construction.max_wall_protection)
+    }
+
+    /// Chance of plague this year, in percent, lowered by the temples.
+    pub fn plague_chance(&self) -> u32 {
+        let rules = &self.rules;
+        if !rules.construction.enabled {
+            return rules.plague_chance;
+        }
+        rules
+            .plague_chance
+            .saturating_sub(self.temples * rules.construction.temple_protection)
+    }
+
+    /// Advances every work under way by a year and reports what was
+    /// finished.
+    pub(crate) fn process_construction(&mut self) -> Vec<YearEvent> {
+        for project in &mut self.projects {
+            project.years_left -= 1;
+        }
+
+        let mut finished = Works::default();
+        self.projects.retain(|project| {
+            if project.years_left == 0 {
+                *finished.count_mut(project.building) += 1;
+            }
+            project.years_left > 0
+        });
+
+        finished
+            .iter()
+            .map(|(building, count)| {
+                *self.built_mut(building) += count;
+                YearEvent::Completed { building, count }
+            })
+            .collect()
+    }
+
+    pub(crate) fn process_raid(&mut self) -> YearEvent {
+        let construction = &self.rules.construction;
+        let rolled = self
+            .rng
+            .gen_range(construction.raid_loss_min..=construction.raid_loss_max);
+        let percent = rolled * (100 - self.wall_protection()) / 100;
+        self.grain_raided = self.grain * percent / 100;
+        self.grain -= self.grain_raided;
+
+        YearEvent::Raid {
+            stolen: self.grain_raided,
+            percent,
+        }
+    }
+
+    /// Pays for the standing buildings. When the stores fall short the
+    /// costliest to keep fall into ruin until the rest can be paid for.
+    pub(crate) fn process_upkeep(&mut self) -> YearEvent {
+        let mut ruined = 0;
+        while self.upkeep() > self.grain {
+            let costliest = Building::ALL
+                .into_iter()
+                .filter(|&building| self.built(building) > 0)
+                .max_by_key(|&building| self.rules.project(building).upkeep);
+            let Some(building) = costliest else {
+                break;
+            };
+            *self.built_mut(building) -= 1;
+            ruined += 1;
+        }
+
+        self.upkeep_paid = self.upkeep();
+        self.grain -= self.upkeep_paid;
+
+        YearEvent::Upkeep {
+            paid: self.upkeep_paid,
+            ruined,
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::actions::{GameAction, Rejection};
+use crate::game::construction::Works;
+use crate::game::events::YearReport;
+use crate::game::rules::Rules;
+use crate::game::scoring::Score;
+use crate::game::state::{GamePhase, GameState};
+
+/// All decisions a ruler makes during a single year. Missing fields read as
+/// zero.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct YearDecisions {
+    /// Acres to buy, or to sell when negative.
+    pub land: i32,
+    /// Bushels to buy with silver once the land is traded, or to sell when
+    /// negative. Only the silver economy trades grain.
+    pub grain: i32,
+    /// Acres to plant with seed.
+    pub plant: u32,
+    /// Bushels given to the people as food.
+    pub feed: u32,
+    /// Tax rate in percent, levied first when the rules tax the people.
+    pub tax: u32,
+    /// Buildings to begin once the trading is done, when the rules allow
+    /// building them.
+    pub granaries: u32,
+    pub canals: u32,
+    pub walls: u32,
+    pub temples: u32,
+}
+
+impl YearDecisions {
+    /// The buildings to begin this year.
+    pub fn works(&self) -> Works {
+        Works {
+            canals: self.canals,
+            walls: self.walls,
+            temples: self.temples,
+            granaries: self.granaries,
+        }
+    }
+
+    /// The decisions as actions, in the order `rules` asks for them.
+    pub fn actions(&self, rules: &Rules) -> Vec<GameAction> {
+        let mut actions = Vec::new();
+
+        if rules.taxation.enabled {
+            actions.push(GameAction::SetTaxRate(self.tax));
+        }
+
+        if self.land > 0 {
+            actions.push(GameAction::BuyLand(self.land as u32));
+        } else if self.land < 0 {
+            actions.push(GameAction::SellLand(self.land.unsigned_abs()));
+        }
+
+        if self.grain > 0 {
+            actions.push(GameAction::BuyGrain(self.grain as u32));
+        } else if self.grain < 0 {
+            actions.push(GameAction::SellGrain(self.grain.unsigned_abs()));
+        }
+
+        if rules.builds() {
+            actions.push(GameAction::Build(self.works()));
+        }
+
+        if rules.is_classic() {
+            actions.push(GameAction::FeedPopulation(self.feed));
+            actions.push(GameAction::PlantAcres(self.plant));
+        } else {
+            actions.push(GameAction::PlantAcres(self.plant));
+            actions.push(GameAction::FeedPopulation(self.feed));
+        }
+        actions
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum YearError {
+    GameOver,
+    Rejected {
+        action: GameAction,
+        reason: Rejection,
+    },
+}
+
+impl fmt::Display for YearError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            YearError::GameOver => write!(f, "the reign is already over"),
+            YearError::Rejected { action, reason } => {
+                write!(f, "{:?} was rejected: {:?}", action, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for YearError {}
+
+/// A reign driven one whole year at a time, without any user interface.
+#[derive(Debug, Clone)]
+pub struct Game {
+    state: GameState,
+}
+
+impl Game {
+    pub fn new(seed: Option<u64>, unlimited: bool) -> Self {
+        Self::with_rules(seed, unlimited, Rules::default())
+    }
+
+    pub fn with_rules(seed: Option<u64>, unlimited: bool, rules: Rules) -> Self {
+        let mut state = GameState::with_rules(seed, unlimited, rules);
+        state.current_phase = state.first_decision_phase();
+        Self { state }
+    }
+
+    pub fn from_state(state: GameState) -> Self {
+        Self { state }
+    }
+
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    pub fn into_state(self) -> GameState {
+        self.state
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.state.current_phase == GamePhase::GameOver
+    }
+
+    /// Applies the decisions and resolves the year. Either every decision is
+    /// accepted and the year is played, or the state is left untouched.
+    pub fn play_year(&mut self, decisions: &YearDecisions) -> Result<YearReport, YearError> {
+        self.play_actions(&decisions.actions(&self.state.rules))
+    }
+
+    pub fn play_actions(&mut self, actions: &[GameAction]) -> Result<YearReport, YearError> {
+        if self.is_over() {
+            return Err(YearError::GameOver);
+        }
+
+        let mut draft = self.state.clone();
+        for action in actions {
+            if let Some(reason) = draft.execute_action(action.clone()).rejection() {
+                return Err(YearError::Rejected {
+                    action: action.clone(),
+                    reason,
+                });
+            }
+        }
+
+        let report = draft.resolve_year();
+        if !report.game_over {
+            draft.advance_year();
+        }
+
+        self.state = draft;
+        Ok(report)
+    }
+
+    pub fn score(&self) -> Score {
+        self.state.final_score()
+    }
+}
@@ -1,69 +1,198 @@
-use crate::game::state::GameState;
-use crate::messages::MessageTemplates;
+use crate::game::construction::Building;
+use crate::game::state::{GamePhase, GameState};
 use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// A single thing that happened to the kingdom while a year was resolved.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum YearEvent {
+    Harvest {
+        yield_per_acre: u32,
+        total: u32,
+    },
+    Rats {
+        eaten: u32,
+        percent: u32,
+    },
+    /// No one arrives (`count` is zero) in a year when people starved.
+    Immigration {
+        count: u32,
+    },
+    Plague {
+        deaths: u32,
+    },
+    Starvation {
+        deaths: u32,
+    },
+    /// Buildings of one kind finished this year.
+    Completed {
+        building: Building,
+        count: u32,
+    },
+    Raid {
+        stolen: u32,
+        percent: u32,
+    },
+    /// Bushels paid to keep the buildings standing, and how many fell into
+    /// ruin when the stores could not pay.
+    Upkeep {
+        paid: u32,
+        ruined: u32,
+    },
+    /// Grain that rotted beyond the granaries' capacity.
+    Spoilage {
+        spoiled: u32,
+    },
+    /// How restless the taxed people have become, and by how much it moved.
+    Unrest {
+        level: u32,
+        change: i32,
+    },
+}
+
+/// Everything that happened to the kingdom while a year was resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YearReport {
+    pub year: u32,
+    pub events: Vec<YearEvent>,
+    pub game_over: bool,
+}
 
 impl GameState {
-    pub fn process_year_events(&mut self, messages: &mut MessageTemplates) -> Vec<String> {
-        let mut outcomes = Vec::new();
+    /// Runs the random events of the year once all decisions are made and
+    /// moves the state to either `YearEnd` or `GameOver`.
+    pub fn resolve_year(&mut self) -> YearReport {
+        let events = self.process_year_events();
+
+        self.save_year_summary();
+
+        let game_over = self.is_game_over();
+        self.current_phase = if game_over {
+            GamePhase::GameOver
+        } else {
+            GamePhase::YearEnd
+        };
+
+        YearReport {
+            year: self.year,
+            events,
+            game_over,
+        }
+    }
+
+    pub fn process_year_events(&mut self) -> Vec<YearEvent> {
+        if self.rules.is_classic() {
+            return self.process_classic_year_events();
+        }
+
+        let mut events = Vec::new();
+
+        // Starvation happened when the people were fed, report it first
+        if self.deaths_starvation > 0 {
+            events.push(YearEvent::Starvation {
+                deaths: self.deaths_starvation,
+            });
+        }
+
+        // Works under way, finished in time for the harvest
+        if self.rules.builds() {
+            events.extend(self.process_construction());
+        }
 
         // Harvest
-        let harvest_outcome = self.process_harvest(messages);
-        outcomes.push(harvest_outcome);
+        events.push(self.process_harvest());
 
-        // Rats (40% chance)
-        if self.rng.gen_range(0..100) < 40 {
-            let rats_outcome = self.process_rats(messages);
-            outcomes.push(rats_outcome);
+        // Rats
+        if self.rng.gen_range(0..100) < self.rules.rats_chance {
+            events.push(self.process_rats());
+        }
+
+        // Raids and upkeep
+        if self.rules.construction.enabled
+            && self.rng.gen_range(0..100) < self.rules.construction.raid_chance
+        {
+            events.push(self.process_raid());
+        }
+        if self.rules.builds() {
+            let upkeep = self.process_upkeep();
+            if !matches!(upkeep, YearEvent::Upkeep { paid: 0, ruined: 0 }) {
+                events.push(upkeep);
+            }
+        }
+
+        // Spoilage
+        if self.rules.granaries.enabled {
+            let spoilage = self.process_spoilage();
+            if self.grain_spoiled > 0 {
+                events.push(spoilage);
+            }
         }
 
         // Immigration
-        let immigration_outcome = self.process_immigration(messages);
-        outcomes.push(immigration_outcome);
+        events.push(self.process_immigration());
+
+        // Plague
+        if self.rng.gen_range(0..100) < self.plague_chance() {
+            events.push(self.process_plague());
+        }
 
-        // Plague (15% chance)
-        if self.rng.gen_range(0..100) < 15 {
-            let plague_outcome = self.process_plague(messages);
-            outcomes.push(plague_outcome);
+        if self.rules.taxation.enabled {
+            events.push(self.process_unrest());
         }
 
-        outcomes
+        events
     }
 
-    fn process_harvest(&mut self, messages: &mut MessageTemplates) -> String {
-        self.harvest_yield = self.rng.gen_range(1..=5);
+    fn process_harvest(&mut self) -> YearEvent {
+        self.harvest_yield = self
+            .rng
+            .gen_range(self.harvest_floor()..=self.rules.harvest_yield_max);
         self.grain_harvested = self.acres_planted * self.harvest_yield;
         self.grain += self.grain_harvested;
 
-        messages.harvest_message(self.harvest_yield, self.grain_harvested)
+        YearEvent::Harvest {
+            yield_per_acre: self.harvest_yield,
+            total: self.grain_harvested,
+        }
     }
 
-    fn process_rats(&mut self, messages: &mut MessageTemplates) -> String {
-        let damage_percent = self.rng.gen_range(10..=30);
+    fn process_rats(&mut self) -> YearEvent {
+        let rolled = self
+            .rng
+            .gen_range(self.rules.rats_damage_min..=self.rules.rats_damage_max);
+        let damage_percent = rolled * (100 - self.rat_protection()) / 100;
         self.grain_eaten_by_rats = self.grain * damage_percent / 100;
         self.grain -= self.grain_eaten_by_rats;
 
-        messages.rats_message(self.grain_eaten_by_rats)
+        YearEvent::Rats {
+            eaten: self.grain_eaten_by_rats,
+            percent: damage_percent,
+        }
     }
 
-    fn process_plague(&mut self, messages: &mut MessageTemplates) -> String {
+    fn process_plague(&mut self) -> YearEvent {
         let deaths = self.population / 2;
         self.deaths_plague = deaths;
         self.population -= deaths;
         self.total_deaths += deaths;
 
-        messages.plague_message()
+        YearEvent::Plague { deaths }
     }
 
-    fn process_immigration(&mut self, messages: &mut MessageTemplates) -> String {
+    fn process_immigration(&mut self) -> YearEvent {
         if self.deaths_starvation > 0 {
             self.new_citizens = 0;
-            return messages.no_immigration_message();
+            return YearEvent::Immigration { count: 0 };
         }
 
         let base_immigration = (20 * self.land + self.grain) / (100 * self.population) + 1;
-        self.new_citizens = base_immigration.min(50);
+        let welcome = 100 - self.immigration_deterred();
+        self.new_citizens = (base_immigration * welcome / 100).min(self.rules.immigration_cap);
         self.population += self.new_citizens;
 
-        messages.immigration_message(self.new_citizens)
+        YearEvent::Immigration {
+            count: self.new_citizens,
+        }
     }
 }
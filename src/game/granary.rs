@@ -0,0 +1,30 @@
+use crate::game::events::YearEvent;
+use crate::game::state::GameState;
+
+impl GameState {
+    /// Bushels the granaries hold without spoiling.
+    pub fn granary_capacity(&self) -> u32 {
+        self.granaries.saturating_mul(self.rules.granaries.capacity)
+    }
+
+    /// Share of the stores rats would have eaten that the granaries save,
+    /// in percent.
+    pub fn rat_protection(&self) -> u32 {
+        let granaries = &self.rules.granaries;
+        if !granaries.enabled {
+            return 0;
+        }
+        (self.granaries * granaries.rat_protection).min(granaries.max_rat_protection)
+    }
+
+    /// Rots part of the grain that the granaries cannot hold.
+    pub(crate) fn process_spoilage(&mut self) -> YearEvent {
+        let excess = self.grain.saturating_sub(self.granary_capacity());
+        self.grain_spoiled = excess * self.rules.granaries.spoilage_percent / 100;
+        self.grain -= self.grain_spoiled;
+
+        YearEvent::Spoilage {
+            spoiled: self.grain_spoiled,
+        }
+    }
+}
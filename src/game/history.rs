@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// The ledger entry for one year of the reign.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct YearRecord {
+    pub year: u32,
+    pub start_grain: u32,
+    pub end_grain: u32,
+    pub start_land: u32,
+    pub end_land: u32,
+    pub start_population: u32,
+    pub end_population: u32,
+    pub land_price: u32,
+    pub start_silver: u32,
+    pub end_silver: u32,
+    pub grain_price: u32,
+    pub tax_rate: u32,
+    pub taxes_collected: u32,
+    pub unrest: u32,
+    pub granaries: u32,
+    pub canals: u32,
+    pub walls: u32,
+    pub temples: u32,
+    pub grain_spoiled: u32,
+    pub grain_raided: u32,
+    pub upkeep_paid: u32,
+    pub acres_planted: u32,
+    pub grain_fed: u32,
+    pub harvest_yield: u32,
+    pub grain_eaten_by_rats: u32,
+    pub new_citizens: u32,
+    pub deaths_starvation: u32,
+    pub deaths_plague: u32,
+}
@@ -0,0 +1,119 @@
+use crate::game::rules::Rules;
+use crate::game::state::{GameRng, GameState};
+use rand::Rng;
+
+/// A price drawn afresh from the rules' range, as every year is priced by
+/// the classic listing and by a market that is switched off.
+pub(crate) fn draw_land_price(rng: &mut GameRng, rules: &Rules) -> u32 {
+    if rules.is_classic() {
+        // C=INT(10*RND(1)): Y=C+17
+        let spread = (rules.land_price_max - rules.land_price_min + 1) as f64;
+        rules.land_price_min + (spread * rng.gen::<f64>()) as u32
+    } else {
+        rng.gen_range(rules.land_price_min..=rules.land_price_max)
+    }
+}
+
+/// A grain price drawn from anywhere in the rules' range.
+pub(crate) fn draw_grain_price(rng: &mut GameRng, rules: &Rules) -> u32 {
+    rng.gen_range(rules.economy.grain_price_min..=rules.economy.grain_price_max)
+}
+
+impl GameState {
+    /// The price land trades at next year. The market drifts back towards
+    /// the middle of the rules' range, rises after a rich harvest, with the
+    /// acres the city bought and with the grain it holds for each mouth,
+    /// falls when it sells, and is shaken by a little chance.
+    pub(crate) fn next_land_price(&mut self) -> u32 {
+        let rules = &self.rules;
+        let market = &rules.market;
+        if rules.is_classic() || !market.dynamic {
+            return draw_land_price(&mut self.rng, rules);
+        }
+
+        let min = rules.land_price_min as f64;
+        let max = rules.land_price_max as f64;
+        let price = self.land_price as f64;
+
+        let reversion = market.reversion as f64 * ((min + max) / 2.0 - price);
+
+        let usual_yield = (rules.harvest_yield_min + rules.harvest_yield_max) as f64 / 2.0;
+        let harvest = market.harvest_weight as f64 * (self.harvest_yield as f64 - usual_yield);
+
+        let net_bought = self.acres_bought as f64 - self.acres_sold as f64;
+        let trade = market.trade_weight as f64 * net_bought / 100.0;
+
+        // Stores beyond a year's rations, in years, within a year either way
+        let rations = (self.population * rules.bushels_per_person) as f64;
+        let surplus = if rations > 0.0 {
+            (self.grain as f64 / rations - 1.0).clamp(-1.0, 1.0)
+        } else {
+            0.0
+        };
+        let prosperity = market.prosperity_weight as f64 * surplus;
+
+        let volatility = market.volatility as f64;
+        let noise = self.rng.gen_range(-volatility..=volatility);
+
+        (price + reversion + harvest + trade + prosperity + noise)
+            .round()
+            .clamp(min, max) as u32
+    }
+
+    /// The price of a hundred bushels next year. Grain is cheap after a
+    /// rich harvest and dear after the city has bought up the market.
+    pub(crate) fn next_grain_price(&mut self) -> u32 {
+        let economy = &self.rules.economy;
+        let min = economy.grain_price_min as f64;
+        let max = economy.grain_price_max as f64;
+        let price = self.grain_price as f64;
+
+        let reversion = self.rules.market.reversion as f64 * ((min + max) / 2.0 - price);
+
+        let usual_yield =
+            (self.rules.harvest_yield_min + self.rules.harvest_yield_max) as f64 / 2.0;
+        let harvest = -economy.harvest_weight as f64 * (self.harvest_yield as f64 - usual_yield);
+
+        let net_bought = self.grain_bought as f64 - self.grain_sold as f64;
+        let trade = economy.trade_weight as f64 * net_bought / 1000.0;
+
+        let volatility = economy.volatility as f64;
+        let noise = self.rng.gen_range(-volatility..=volatility);
+
+        (price + reversion + harvest + trade + noise)
+            .round()
+            .clamp(min, max) as u32
+    }
+
+    /// The price of land in every year of the reign so far, this year's
+    /// included.
+    pub fn price_history(&self) -> Vec<u32> {
+        let mut prices: Vec<u32> = self
+            .history
+            .iter()
+            .map(|record| record.land_price)
+            .collect();
+        if self.history.last().map(|record| record.year) != Some(self.year) {
+            prices.push(self.land_price);
+        }
+        prices
+    }
+
+    /// How far this year's price moved from last year's, once there is one.
+    pub fn price_change(&self) -> Option<i64> {
+        let prices = self.price_history();
+        match prices.as_slice() {
+            [.., last, current] => Some(*current as i64 - *last as i64),
+            _ => None,
+        }
+    }
+}
+
+/// A word for the way the price moved, as the market criers call it.
+pub fn describe_price_change(change: i64) -> String {
+    match change {
+        0 => "STEADY".to_string(),
+        up if up > 0 => format!("UP {}", up),
+        down => format!("DOWN {}", -down),
+    }
+}
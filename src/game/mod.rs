@@ -1,8 +1,35 @@
 pub mod actions;
+pub mod classic;
+pub mod construction;
+pub mod engine;
 pub mod events;
+pub mod granary;
+pub mod history;
+pub mod market;
+pub mod projection;
+pub mod replay;
+pub mod rules;
 pub mod scoring;
+pub mod simulation;
 pub mod state;
+pub mod strategy;
+pub mod taxation;
 
-pub use actions::{ActionResult, GameAction};
-pub use scoring::evaluate_performance;
-pub use state::{GamePhase, GameState};
+pub use actions::{ActionResult, GameAction, Rejection};
+pub use construction::{Building, Project, Works};
+pub use engine::{Game, YearDecisions, YearError};
+pub use events::{YearEvent, YearReport};
+pub use history::YearRecord;
+pub use market::describe_price_change;
+pub use projection::Projection;
+pub use replay::{Replay, RULES_VERSION};
+pub use rules::{
+    ConstructionRules, EconomyRules, GranaryRules, MarketRules, ProjectRules, Rules, Ruleset,
+    ScoringRules, TaxRules,
+};
+pub use scoring::{evaluate_performance, rate_performance, PerformanceRating, Score};
+pub use simulation::{simulate, SimulationStats};
+pub use state::{GamePhase, GameRng, GameState};
+pub use strategy::{
+    builtin, FeedEveryone, MaxPlanting, Optimal, Policy, Speculator, Strategy, BUILTIN_STRATEGIES,
+};
@@ -0,0 +1,86 @@
+use crate::game::actions::{GameAction, Rejection};
+use crate::game::engine::YearDecisions;
+use crate::game::state::{GamePhase, GameState};
+
+/// What a year's decisions would leave behind, worked out before any of them
+/// is taken. Only the harvest is uncertain, so it is given as a range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Projection {
+    /// Grain left in store once land is traded, fields sown and people fed.
+    pub grain_remaining: u32,
+    pub land: u32,
+    /// Most acres that could be sown after the trade and the building with
+    /// the grain that is not set aside for feeding.
+    pub max_plantable_acres: u32,
+    /// Bushels short of feeding everyone.
+    pub feeding_shortfall: u32,
+    pub harvest_min: u32,
+    pub harvest_max: u32,
+    pub starvation: u32,
+    /// Whether the famine alone would end the reign.
+    pub impeachment: bool,
+    /// The first decision the stores, land or people could not carry out.
+    pub rejection: Option<(GameAction, Rejection)>,
+}
+
+impl GameState {
+    pub fn project(&self, decisions: &YearDecisions) -> Projection {
+        let mut draft = self.clone();
+        let mut rejection = None;
+        for action in decisions.actions(&self.rules) {
+            if matches!(action, GameAction::SetTaxRate(_))
+                && self.current_phase != GamePhase::Taxation
+            {
+                // The year's tax is already levied
+                continue;
+            }
+            if let Some(reason) = draft.execute_action(action.clone()).rejection() {
+                rejection.get_or_insert((action, reason));
+            }
+        }
+
+        let mut traded = self.clone();
+        let trades = YearDecisions {
+            land: decisions.land,
+            grain: decisions.grain,
+            granaries: decisions.granaries,
+            canals: decisions.canals,
+            walls: decisions.walls,
+            temples: decisions.temples,
+            ..YearDecisions::default()
+        };
+        for action in trades.actions(&self.rules) {
+            if matches!(
+                action,
+                GameAction::BuyLand(_)
+                    | GameAction::SellLand(_)
+                    | GameAction::BuyGrain(_)
+                    | GameAction::SellGrain(_)
+                    | GameAction::Build(_)
+            ) {
+                traded.execute_action(action);
+            }
+        }
+        let sowable_grain = traded.grain.saturating_sub(decisions.feed);
+        let max_plantable_acres = traded
+            .workable_acres()
+            .min(traded.land)
+            .min(self.rules.acres_sown_with(sowable_grain));
+
+        let starvation = self.starvation_for(decisions.feed);
+
+        Projection {
+            grain_remaining: draft.grain,
+            land: draft.land,
+            max_plantable_acres,
+            feeding_shortfall: self
+                .grain_needed_for_feeding()
+                .saturating_sub(decisions.feed),
+            harvest_min: decisions.plant.saturating_mul(self.harvest_floor()),
+            harvest_max: decisions.plant.saturating_mul(self.rules.harvest_yield_max),
+            starvation,
+            impeachment: self.rules.impeaches(starvation, self.population),
+            rejection,
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::game::actions::GameAction;
+use crate::game::engine::{Game, YearError};
+use crate::game::rules::Rules;
+use crate::game::scoring::Score;
+
+/// Bumped whenever a change to the rules would make old replays play out
+/// differently.
+pub const RULES_VERSION: u32 = 3;
+
+/// Everything needed to play a reign again: the seed it started from and
+/// the actions taken in each year.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub rules_version: u32,
+    pub seed: u64,
+    pub unlimited: bool,
+    /// The ruleset the reign was played under.
+    #[serde(default)]
+    pub rules: Rules,
+    pub years: Vec<Vec<GameAction>>,
+    /// The score the reign ended with, if it was played to the end.
+    pub score: Option<Score>,
+}
+
+impl Replay {
+    pub fn new(seed: u64, unlimited: bool, rules: Rules) -> Self {
+        Self {
+            rules_version: RULES_VERSION,
+            seed,
+            unlimited,
+            rules,
+            years: Vec::new(),
+            score: None,
+        }
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read replay {}", path.display()))?;
+        let replay: Replay = serde_json::from_str(&contents)
+            .with_context(|| format!("failed to parse replay {}", path.display()))?;
+
+        if replay.rules_version != RULES_VERSION {
+            bail!(
+                "replay {} was recorded with rules version {}, expected {}",
+                path.display(),
+                replay.rules_version,
+                RULES_VERSION
+            );
+        }
+
+        Ok(replay)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(path, contents)
+            .with_context(|| format!("failed to write replay {}", path.display()))
+    }
+
+    /// Plays every recorded year without a user interface.
+    pub fn simulate(&self) -> Result<Game, YearError> {
+        let mut game = Game::with_rules(Some(self.seed), self.unlimited, self.rules.clone());
+        for actions in &self.years {
+            game.play_actions(actions)?;
+        }
+        Ok(game)
+    }
+
+    /// Re-simulates the reign and checks it ends with the recorded score.
+    pub fn verify(&self) -> Result<Score> {
+        let Some(expected) = &self.score else {
+            bail!("replay has no final score, the reign was not finished");
+        };
+
+        let game = self.simulate()?;
+        if !game.is_over() {
+            bail!("replay ended before the reign was over");
+        }
+
+        let score = game.score();
+        if &score != expected {
+            bail!(
+                "replay diverged: recorded {:?}, simulated {:?}",
+                expected,
+                score
+            );
+        }
+
+        Ok(score)
+    }
+}
@@ -0,0 +1,584 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::game::construction::{Building, Works};
+
+/// Every balance constant of the game. Missing keys in a rules file fall
+/// back to the default ruleset, which is the one the game ships with, and
+/// unknown keys are refused so a misspelling does not go unnoticed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Rules {
+    /// Which arithmetic resolves the year. Everything below still applies
+    /// to the classic ruleset, except where its listing hard-codes a value.
+    pub ruleset: Ruleset,
+
+    pub starting_population: u32,
+    pub starting_grain: u32,
+    pub starting_land: u32,
+    /// Reign length in years, ignored in unlimited mode.
+    pub years: u32,
+
+    pub land_price_min: u32,
+    pub land_price_max: u32,
+    pub harvest_yield_min: u32,
+    pub harvest_yield_max: u32,
+
+    /// Chance of rats each year, in percent.
+    pub rats_chance: u32,
+    /// Share of the stores rats eat, in percent.
+    pub rats_damage_min: u32,
+    pub rats_damage_max: u32,
+    /// Chance of plague each year, in percent.
+    pub plague_chance: u32,
+    /// Most newcomers that arrive in a single year.
+    pub immigration_cap: u32,
+
+    pub bushels_per_person: u32,
+    pub acres_per_worker: u32,
+    /// Acres sown with a single bushel of seed.
+    pub acres_per_seed_bushel: u32,
+    /// Share of the people that may starve in one year before the ruler is
+    /// impeached, in percent.
+    pub impeachment_percent: u32,
+
+    /// How the price of land moves between its bounds.
+    pub market: MarketRules,
+    pub economy: EconomyRules,
+    pub taxation: TaxRules,
+    pub granaries: GranaryRules,
+    pub construction: ConstructionRules,
+    pub scoring: ScoringRules,
+}
+
+/// The family of formulas the year is resolved with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Ruleset {
+    #[default]
+    Modern,
+    /// The 1968/1973 BASIC program, arithmetic and messages alike: grain is
+    /// fed before planting, rats strike on an even roll, newcomers depend on
+    /// a random factor and plague halves the city one year in five.
+    Classic,
+}
+
+/// The land market of the modern ruleset. Each year the price closes
+/// `reversion` of its distance to the middle of the price range, moves by
+/// `harvest_weight` bushels for every bushel the harvest yielded above or
+/// below the usual, by `trade_weight` for every hundred acres the city
+/// bought or sold and by up to `prosperity_weight` as its stores exceed or
+/// fall short of a year's rations, then by a random amount of at most
+/// `volatility`. When `dynamic` is off every year's price is drawn afresh.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MarketRules {
+    pub dynamic: bool,
+    pub reversion: f32,
+    pub harvest_weight: f32,
+    pub trade_weight: f32,
+    pub prosperity_weight: f32,
+    pub volatility: f32,
+}
+
+/// The optional silver economy. When `enabled` the city keeps a treasury
+/// of silver shekels besides its grain: land is bought and sold for silver,
+/// and grain can be traded for it at a price per hundred bushels that moves
+/// between `grain_price_min` and `grain_price_max`. The grain price closes
+/// the market's `reversion` towards the middle, falls by `harvest_weight`
+/// for every bushel the harvest yielded above the usual, rises by
+/// `trade_weight` for every thousand bushels the city bought and varies by
+/// at most `volatility`. Not available to the classic ruleset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EconomyRules {
+    pub enabled: bool,
+    pub starting_silver: u32,
+    pub grain_price_min: u32,
+    pub grain_price_max: u32,
+    pub harvest_weight: f32,
+    pub trade_weight: f32,
+    pub volatility: f32,
+}
+
+/// The optional taxation decision that opens each year. The ruler levies up
+/// to `max_rate` percent, collecting `yield_per_person` bushels (or shekels
+/// under the silver economy) from every subject at a full rate. Each point
+/// of tax keeps away `immigration_penalty` percent of the year's newcomers.
+/// Every point above `tolerated_rate` raises unrest by `unrest_per_point`,
+/// lighter years calm it by `unrest_decay`, and once it reaches
+/// `revolt_unrest` the people overthrow the ruler.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TaxRules {
+    pub enabled: bool,
+    pub max_rate: u32,
+    pub yield_per_person: u32,
+    pub immigration_penalty: u32,
+    pub tolerated_rate: u32,
+    pub unrest_per_point: u32,
+    pub unrest_decay: u32,
+    pub revolt_unrest: u32,
+}
+
+/// The optional granaries. When `enabled` the city starts with
+/// `starting_granaries`, each storing `capacity` bushels, and every year
+/// `spoilage_percent` of the grain beyond what they hold rots. Each granary
+/// cuts the share of the stores rats eat by `rat_protection` percent, up to
+/// `max_rat_protection`. Building one costs `grain_cost` bushels and keeps
+/// `workers` people from the fields for the year. Not available to the
+/// classic ruleset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GranaryRules {
+    pub enabled: bool,
+    pub starting_granaries: u32,
+    pub capacity: u32,
+    pub spoilage_percent: u32,
+    pub rat_protection: u32,
+    pub max_rat_protection: u32,
+    pub grain_cost: u32,
+    pub workers: u32,
+    /// Years a granary takes to build, and bushels it costs each year once
+    /// standing.
+    pub years: u32,
+    pub upkeep: u32,
+}
+
+/// The optional building works besides granaries. Canals each raise the
+/// poorest harvest by `canal_yield` bushels an acre, never beyond the best.
+/// Raiders strike in `raid_chance` percent of years and carry off
+/// `raid_loss_min` to `raid_loss_max` percent of the stores, of which each
+/// wall saves `wall_protection` percent, up to `max_wall_protection`. Each
+/// temple lowers the chance of plague by `temple_protection` points. Not
+/// available to the classic ruleset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConstructionRules {
+    pub enabled: bool,
+    pub canal: ProjectRules,
+    pub wall: ProjectRules,
+    pub temple: ProjectRules,
+    pub canal_yield: u32,
+    pub raid_chance: u32,
+    pub raid_loss_min: u32,
+    pub raid_loss_max: u32,
+    pub wall_protection: u32,
+    pub max_wall_protection: u32,
+    pub temple_protection: u32,
+}
+
+/// What one building costs: `grain_cost` bushels when it is begun,
+/// `workers` people kept from the fields in each of the `years` it takes,
+/// and `upkeep` bushels every year once it stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectRules {
+    pub grain_cost: u32,
+    pub workers: u32,
+    pub years: u32,
+    pub upkeep: u32,
+}
+
+/// Thresholds for the final rating. A reign is rated terrible when its death
+/// rate exceeds `terrible_death_rate` percent or it ends with fewer than
+/// `terrible_acres` acres per person, then poor, fair and good are checked
+/// the same way. Anything better is excellent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScoringRules {
+    pub terrible_death_rate: f32,
+    pub terrible_acres: f32,
+    pub poor_death_rate: f32,
+    pub poor_acres: f32,
+    pub fair_death_rate: f32,
+    pub fair_acres: f32,
+    pub good_death_rate: f32,
+    pub good_acres: f32,
+    /// Mean unrest above which a taxed reign is rated one tier lower.
+    pub unrest: f32,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            ruleset: Ruleset::Modern,
+            starting_population: 100,
+            starting_grain: 2800,
+            starting_land: 1000,
+            years: 10,
+            land_price_min: 17,
+            land_price_max: 26,
+            harvest_yield_min: 1,
+            harvest_yield_max: 5,
+            rats_chance: 40,
+            rats_damage_min: 10,
+            rats_damage_max: 30,
+            plague_chance: 15,
+            immigration_cap: 50,
+            bushels_per_person: 20,
+            acres_per_worker: 10,
+            acres_per_seed_bushel: 1,
+            impeachment_percent: 45,
+            market: MarketRules::default(),
+            economy: EconomyRules::default(),
+            taxation: TaxRules::default(),
+            granaries: GranaryRules::default(),
+            construction: ConstructionRules::default(),
+            scoring: ScoringRules::default(),
+        }
+    }
+}
+
+impl Default for MarketRules {
+    fn default() -> Self {
+        Self {
+            dynamic: true,
+            reversion: 0.25,
+            harvest_weight: 1.0,
+            trade_weight: 2.0,
+            prosperity_weight: 2.0,
+            volatility: 3.0,
+        }
+    }
+}
+
+impl Default for EconomyRules {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            starting_silver: 1000,
+            grain_price_min: 60,
+            grain_price_max: 140,
+            harvest_weight: 10.0,
+            trade_weight: 5.0,
+            volatility: 10.0,
+        }
+    }
+}
+
+impl Default for TaxRules {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_rate: 50,
+            yield_per_person: 20,
+            immigration_penalty: 2,
+            tolerated_rate: 10,
+            unrest_per_point: 1,
+            unrest_decay: 10,
+            revolt_unrest: 100,
+        }
+    }
+}
+
+impl Default for GranaryRules {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            starting_granaries: 2,
+            capacity: 1500,
+            spoilage_percent: 50,
+            rat_protection: 10,
+            max_rat_protection: 60,
+            grain_cost: 300,
+            workers: 5,
+            years: 1,
+            upkeep: 0,
+        }
+    }
+}
+
+impl Default for ConstructionRules {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            canal: ProjectRules {
+                grain_cost: 500,
+                workers: 10,
+                years: 2,
+                upkeep: 20,
+            },
+            wall: ProjectRules {
+                grain_cost: 400,
+                workers: 10,
+                years: 2,
+                upkeep: 10,
+            },
+            temple: ProjectRules {
+                grain_cost: 600,
+                workers: 8,
+                years: 3,
+                upkeep: 30,
+            },
+            canal_yield: 1,
+            raid_chance: 20,
+            raid_loss_min: 10,
+            raid_loss_max: 30,
+            wall_protection: 25,
+            max_wall_protection: 75,
+            temple_protection: 4,
+        }
+    }
+}
+
+impl Default for ProjectRules {
+    fn default() -> Self {
+        Self {
+            grain_cost: 500,
+            workers: 10,
+            years: 2,
+            upkeep: 20,
+        }
+    }
+}
+
+impl Default for ScoringRules {
+    fn default() -> Self {
+        Self {
+            terrible_death_rate: 33.0,
+            terrible_acres: 7.0,
+            poor_death_rate: 20.0,
+            poor_acres: 9.0,
+            fair_death_rate: 10.0,
+            fair_acres: 10.0,
+            good_death_rate: 3.0,
+            good_acres: 12.0,
+            unrest: 25.0,
+        }
+    }
+}
+
+impl Rules {
+    /// The constants of the BASIC listing: 95 people plus 5 newcomers, seed
+    /// at two acres a bushel and no cap on immigration. The chances only
+    /// describe the listing; the classic arithmetic rolls its own dice.
+    pub fn classic() -> Self {
+        Self {
+            ruleset: Ruleset::Classic,
+            market: MarketRules {
+                dynamic: false,
+                ..MarketRules::default()
+            },
+            rats_chance: 40,
+            rats_damage_min: 25,
+            rats_damage_max: 50,
+            plague_chance: 20,
+            immigration_cap: u32::MAX,
+            acres_per_seed_bushel: 2,
+            scoring: ScoringRules {
+                terrible_death_rate: 33.0,
+                terrible_acres: 7.0,
+                poor_death_rate: 10.0,
+                poor_acres: 9.0,
+                fair_death_rate: 3.0,
+                fair_acres: 10.0,
+                // The listing has no tier between fair and fantastic
+                good_death_rate: 3.0,
+                good_acres: 10.0,
+                ..ScoringRules::default()
+            },
+            ..Self::default()
+        }
+    }
+
+    /// Rules by name, as accepted by `--ruleset`.
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "modern" => Some(Self::default()),
+            "classic" => Some(Self::classic()),
+            _ => None,
+        }
+    }
+
+    pub fn is_classic(&self) -> bool {
+        self.ruleset == Ruleset::Classic
+    }
+
+    /// Whether land is paid for in silver and grain can be traded.
+    pub fn uses_silver(&self) -> bool {
+        self.economy.enabled && !self.is_classic()
+    }
+
+    /// Whether the year asks what to build.
+    pub fn builds(&self) -> bool {
+        Building::ALL
+            .into_iter()
+            .any(|building| self.can_build(building))
+    }
+
+    /// The kinds of building the year may begin.
+    pub fn buildable(&self) -> Vec<Building> {
+        Building::ALL
+            .into_iter()
+            .filter(|&building| self.can_build(building))
+            .collect()
+    }
+
+    pub fn can_build(&self, building: Building) -> bool {
+        let enabled = match building {
+            Building::Granary => self.granaries.enabled,
+            Building::Canal | Building::Wall | Building::Temple => self.construction.enabled,
+        };
+        enabled && !self.is_classic()
+    }
+
+    /// What a building of the kind costs.
+    pub fn project(&self, building: Building) -> ProjectRules {
+        match building {
+            Building::Canal => self.construction.canal,
+            Building::Wall => self.construction.wall,
+            Building::Temple => self.construction.temple,
+            Building::Granary => ProjectRules {
+                grain_cost: self.granaries.grain_cost,
+                workers: self.granaries.workers,
+                years: self.granaries.years,
+                upkeep: self.granaries.upkeep,
+            },
+        }
+    }
+
+    /// Bushels paid to begin `works`, or `None` if that is beyond counting.
+    pub fn works_cost(&self, works: &Works) -> Option<u32> {
+        works.iter().try_fold(0u32, |total, (building, count)| {
+            total.checked_add(count.checked_mul(self.project(building).grain_cost)?)
+        })
+    }
+
+    /// People kept from the fields while `works` are built, or `None` if
+    /// that is beyond counting.
+    pub fn works_labor(&self, works: &Works) -> Option<u32> {
+        works.iter().try_fold(0u32, |total, (building, count)| {
+            total.checked_add(count.checked_mul(self.project(building).workers)?)
+        })
+    }
+
+    /// What land is paid for with, as the steward names it.
+    pub fn land_currency(&self) -> &'static str {
+        if self.uses_silver() {
+            "SHEKELS"
+        } else {
+            "BUSHELS"
+        }
+    }
+
+    pub fn from_toml_file(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read rules {}", path.display()))?;
+        let rules: Rules = toml::from_str(&contents)
+            .with_context(|| format!("failed to parse rules {}", path.display()))?;
+
+        rules
+            .validate()
+            .with_context(|| format!("invalid rules {}", path.display()))?;
+        Ok(rules)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.starting_population == 0 {
+            bail!("starting_population must be positive");
+        }
+        if self.years == 0 {
+            bail!("years must be positive");
+        }
+        if self.land_price_min == 0 || self.land_price_min > self.land_price_max {
+            bail!("land prices must satisfy 0 < land_price_min <= land_price_max");
+        }
+        if !(0.0..=1.0).contains(&self.market.reversion) {
+            bail!("market.reversion must lie between 0 and 1");
+        }
+        if self.market.volatility < 0.0 || self.market.volatility.is_nan() {
+            bail!("market.volatility must not be negative");
+        }
+        if self.economy.enabled && self.is_classic() {
+            bail!("the classic ruleset has no silver economy");
+        }
+        if self.taxation.enabled && self.is_classic() {
+            bail!("the classic ruleset levies no taxes");
+        }
+        if self.taxation.max_rate > 100 || self.taxation.tolerated_rate > self.taxation.max_rate {
+            bail!("tax rates must satisfy tolerated_rate <= max_rate <= 100");
+        }
+        if self.taxation.revolt_unrest == 0 {
+            bail!("taxation.revolt_unrest must be positive");
+        }
+        if self.granaries.enabled && self.is_classic() {
+            bail!("the classic ruleset has no granaries");
+        }
+        if self.granaries.capacity == 0 {
+            bail!("granaries.capacity must be positive");
+        }
+        if self.granaries.spoilage_percent > 100 || self.granaries.max_rat_protection > 100 {
+            bail!("granary spoilage and rat protection must not exceed 100 percent");
+        }
+        if self.construction.enabled && self.is_classic() {
+            bail!("the classic ruleset builds no works");
+        }
+        let c = &self.construction;
+        if c.raid_loss_min > c.raid_loss_max || c.raid_loss_max > 100 {
+            bail!("raid losses must satisfy raid_loss_min <= raid_loss_max <= 100");
+        }
+        if c.raid_chance > 100 || c.max_wall_protection > 100 {
+            bail!("construction chances and protection must not exceed 100 percent");
+        }
+        if Building::ALL
+            .into_iter()
+            .any(|building| self.project(building).years == 0)
+        {
+            bail!("every building must take at least one year");
+        }
+        if self.economy.grain_price_min == 0
+            || self.economy.grain_price_min > self.economy.grain_price_max
+        {
+            bail!("grain prices must satisfy 0 < grain_price_min <= grain_price_max");
+        }
+        if self.economy.volatility < 0.0 || self.economy.volatility.is_nan() {
+            bail!("economy.volatility must not be negative");
+        }
+        if self.harvest_yield_min > self.harvest_yield_max {
+            bail!("harvest_yield_min must not exceed harvest_yield_max");
+        }
+        if self.rats_damage_min > self.rats_damage_max || self.rats_damage_max > 100 {
+            bail!("rats damage must satisfy rats_damage_min <= rats_damage_max <= 100");
+        }
+        if self.rats_chance > 100 || self.plague_chance > 100 || self.impeachment_percent > 100 {
+            bail!("chances and percentages must not exceed 100");
+        }
+        if self.bushels_per_person == 0 || self.acres_per_seed_bushel == 0 {
+            bail!("bushels_per_person and acres_per_seed_bushel must be positive");
+        }
+        Ok(())
+    }
+
+    /// Whether `deaths` of `population` starving in one year ends the reign.
+    /// The classic listing compares without rounding the share down.
+    pub fn impeaches(&self, deaths: u32, population: u32) -> bool {
+        if self.is_classic() {
+            deaths * 100 > self.impeachment_percent * population
+        } else {
+            deaths > 0 && deaths * 100 / population > self.impeachment_percent
+        }
+    }
+
+    /// Bushels of seed needed to sow `acres`, rounded down.
+    pub fn seed_for(&self, acres: u32) -> u32 {
+        acres / self.acres_per_seed_bushel
+    }
+
+    /// Most acres that `grain` bushels of seed can sow.
+    pub fn acres_sown_with(&self, grain: u32) -> u32 {
+        (grain.saturating_add(1))
+            .saturating_mul(self.acres_per_seed_bushel)
+            .saturating_sub(1)
+    }
+
+    /// The land price `numerator / denominator` of the way from the
+    /// cheapest to the dearest, rounded to the nearest.
+    pub fn land_price_at(&self, numerator: u32, denominator: u32) -> u32 {
+        let span = self.land_price_max.saturating_sub(self.land_price_min);
+        self.land_price_min + (2 * span * numerator + denominator) / (2 * denominator)
+    }
+}
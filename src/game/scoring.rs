@@ -1,12 +1,19 @@
-#[derive(Debug, Clone)]
+use crate::game::rules::Rules;
+use crate::game::state::GameState;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Score {
     pub total_deaths: u32,
     pub death_rate: f32,
     pub acres_per_person: f32,
     pub rating: PerformanceRating,
+    /// Unrest averaged over a taxed reign, zero when no taxes are levied.
+    #[serde(default)]
+    pub mean_unrest: f32,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum PerformanceRating {
     Terrible,
     Poor,
@@ -16,35 +23,83 @@ pub enum PerformanceRating {
 }
 
 pub fn evaluate_performance(
-    initial_population: u32,
+    rules: &Rules,
     final_population: u32,
     total_deaths: u32,
     final_land: u32,
 ) -> Score {
-    let death_rate = (total_deaths as f32) / (initial_population as f32 * 10.0) * 100.0;
+    let death_rate =
+        (total_deaths as f32) / (rules.starting_population as f32 * rules.years as f32) * 100.0;
     let acres_per_person = if final_population > 0 {
         final_land as f32 / final_population as f32
     } else {
         0.0
     };
 
-    let rating = if death_rate > 33.0 || acres_per_person < 7.0 {
+    Score {
+        total_deaths,
+        death_rate,
+        acres_per_person,
+        rating: rate_performance(rules, death_rate, acres_per_person),
+        mean_unrest: 0.0,
+    }
+}
+
+pub fn rate_performance(
+    rules: &Rules,
+    death_rate: f32,
+    acres_per_person: f32,
+) -> PerformanceRating {
+    let t = &rules.scoring;
+    if death_rate > t.terrible_death_rate || acres_per_person < t.terrible_acres {
         PerformanceRating::Terrible
-    } else if death_rate > 20.0 || acres_per_person < 9.0 {
+    } else if death_rate > t.poor_death_rate || acres_per_person < t.poor_acres {
         PerformanceRating::Poor
-    } else if death_rate > 10.0 || acres_per_person < 10.0 {
+    } else if death_rate > t.fair_death_rate || acres_per_person < t.fair_acres {
         PerformanceRating::Fair
-    } else if death_rate > 3.0 || acres_per_person < 12.0 {
+    } else if death_rate > t.good_death_rate || acres_per_person < t.good_acres {
         PerformanceRating::Good
     } else {
         PerformanceRating::Excellent
-    };
+    }
+}
 
-    Score {
-        total_deaths,
-        death_rate,
-        acres_per_person,
-        rating,
+impl PerformanceRating {
+    /// The tier below, or terrible at the bottom.
+    pub fn lowered(&self) -> Self {
+        match self {
+            PerformanceRating::Terrible | PerformanceRating::Poor => PerformanceRating::Terrible,
+            PerformanceRating::Fair => PerformanceRating::Poor,
+            PerformanceRating::Good => PerformanceRating::Fair,
+            PerformanceRating::Excellent => PerformanceRating::Good,
+        }
+    }
+}
+
+impl GameState {
+    pub fn final_score(&self) -> Score {
+        if self.rules.is_classic() {
+            // P1 and D1 of the listing count starvation alone
+            let death_rate = self.classic_starvation_average() as f32;
+            let acres_per_person = self.land as f32 / self.population.max(1) as f32;
+            return Score {
+                total_deaths: self.classic_starvation_total(),
+                death_rate,
+                acres_per_person,
+                rating: rate_performance(&self.rules, death_rate, acres_per_person),
+                mean_unrest: 0.0,
+            };
+        }
+
+        let mut score =
+            evaluate_performance(&self.rules, self.population, self.total_deaths, self.land);
+        if self.rules.taxation.enabled {
+            score.mean_unrest = self.mean_unrest();
+            if score.mean_unrest > self.rules.scoring.unrest {
+                score.rating = score.rating.lowered();
+            }
+        }
+        score
     }
 }
 
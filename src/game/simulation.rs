@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::engine::{Game, YearError};
+use crate::game::rules::Rules;
+use crate::game::scoring::PerformanceRating;
+use crate::game::strategy::Strategy;
+
+/// Aggregate results of many simulated reigns.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SimulationStats {
+    pub games: u32,
+    /// Reigns that lasted their full term without impeachment, revolt or
+    /// extinction.
+    pub survived: u32,
+    pub terrible: u32,
+    pub poor: u32,
+    pub fair: u32,
+    pub good: u32,
+    pub excellent: u32,
+    pub total_deaths: u64,
+    pub total_acres_per_person: f64,
+}
+
+impl SimulationStats {
+    pub fn survival_rate(&self) -> f64 {
+        self.ratio(self.survived as f64)
+    }
+
+    pub fn mean_deaths(&self) -> f64 {
+        self.ratio(self.total_deaths as f64)
+    }
+
+    pub fn mean_acres_per_person(&self) -> f64 {
+        self.ratio(self.total_acres_per_person)
+    }
+
+    fn ratio(&self, total: f64) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            total / self.games as f64
+        }
+    }
+
+    fn record(&mut self, game: &Game) {
+        let state = game.state();
+        let score = game.score();
+
+        self.games += 1;
+        if state.population > 0 && !state.was_impeached() && !state.was_overthrown() {
+            self.survived += 1;
+        }
+
+        match score.rating {
+            PerformanceRating::Terrible => self.terrible += 1,
+            PerformanceRating::Poor => self.poor += 1,
+            PerformanceRating::Fair => self.fair += 1,
+            PerformanceRating::Good => self.good += 1,
+            PerformanceRating::Excellent => self.excellent += 1,
+        }
+
+        self.total_deaths += score.total_deaths as u64;
+        self.total_acres_per_person += score.acres_per_person as f64;
+    }
+}
+
+/// Plays `games` full-length reigns seeded `first_seed`, `first_seed + 1`, ...
+/// Fails as soon as the strategy makes a decision the kingdom cannot afford.
+pub fn simulate(
+    games: u32,
+    first_seed: u64,
+    rules: &Rules,
+    strategy: &mut dyn Strategy,
+) -> Result<SimulationStats, YearError> {
+    let mut stats = SimulationStats::default();
+
+    for i in 0..games {
+        let seed = first_seed.wrapping_add(i as u64);
+        let mut game = Game::with_rules(Some(seed), false, rules.clone());
+        while !game.is_over() {
+            let decisions = strategy.decide(game.state());
+            game.play_year(&decisions)?;
+        }
+        stats.record(&game);
+    }
+
+    Ok(stats)
+}
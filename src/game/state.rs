@@ -1,60 +1,163 @@
-use rand::rngs::StdRng;
-use rand::{Rng, SeedableRng};
+use crate::game::construction::Project;
+use crate::game::history::YearRecord;
+use crate::game::market::{draw_grain_price, draw_land_price};
+use crate::game::rules::Rules;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+/// The generator behind every random draw. It produces the same stream as
+/// rand's `StdRng` for a given seed, but its position can be serialized.
+pub type GameRng = ChaCha12Rng;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameState {
     pub year: u32,
     pub population: u32,
     pub grain: u32,
     pub land: u32,
     pub land_price: u32,
+    /// Shekels of silver in the treasury, kept only by the silver economy.
+    pub silver: u32,
+    /// Shekels a hundred bushels of grain trade for, or zero without the
+    /// silver economy.
+    pub grain_price: u32,
+    /// Percent levied this year, kept from year to year.
+    pub tax_rate: u32,
+    pub taxes_collected: u32,
+    /// How restless the people are, from zero up to the rules' revolt.
+    pub unrest: u32,
+    /// Buildings standing, and those still under way.
+    pub granaries: u32,
+    pub canals: u32,
+    pub walls: u32,
+    pub temples: u32,
+    pub projects: Vec<Project>,
     pub harvest_yield: u32,
     pub grain_eaten_by_rats: u32,
+    /// Bushels that rotted this year for want of granary space.
+    pub grain_spoiled: u32,
+    /// Bushels carried off by raiders and paid to keep the buildings.
+    pub grain_raided: u32,
+    pub upkeep_paid: u32,
     pub new_citizens: u32,
     pub deaths_starvation: u32,
     pub deaths_plague: u32,
     pub total_deaths: u32,
     pub grain_harvested: u32,
     pub acres_planted: u32,
-    pub rng: StdRng,
+    pub grain_fed: u32,
+    /// Acres traded this year, which move next year's price.
+    pub acres_bought: u32,
+    pub acres_sold: u32,
+    /// Bushels traded for silver this year.
+    pub grain_bought: u32,
+    pub grain_sold: u32,
+    pub start_grain: u32,
+    pub start_land: u32,
+    pub start_population: u32,
+    pub start_silver: u32,
+    pub history: Vec<YearRecord>,
+    pub rules: Rules,
+    pub rng: GameRng,
     pub current_phase: GamePhase,
     pub unlimited_mode: bool,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum GamePhase {
     Splash,
     Instructions,
+    Taxation,
     LandTransaction,
+    Construction,
     Planting,
     Feeding,
     YearEnd,
     GameOver,
 }
 
+impl GamePhase {
+    /// Whether the ruler is being asked for one of the year's decisions.
+    pub fn is_decision(&self) -> bool {
+        matches!(
+            self,
+            GamePhase::Taxation
+                | GamePhase::LandTransaction
+                | GamePhase::Construction
+                | GamePhase::Planting
+                | GamePhase::Feeding
+        )
+    }
+}
+
 impl GameState {
     pub fn new(seed: Option<u64>, unlimited: bool) -> Self {
+        Self::with_rules(seed, unlimited, Rules::default())
+    }
+
+    pub fn with_rules(seed: Option<u64>, unlimited: bool, rules: Rules) -> Self {
         let mut rng = match seed {
-            Some(s) => StdRng::seed_from_u64(s),
-            None => StdRng::from_entropy(),
+            Some(s) => GameRng::seed_from_u64(s),
+            None => GameRng::from_entropy(),
         };
 
-        let initial_land_price = rng.gen_range(17..=26);
+        let initial_land_price = draw_land_price(&mut rng, &rules);
+        let (silver, grain_price) = if rules.uses_silver() {
+            (
+                rules.economy.starting_silver,
+                draw_grain_price(&mut rng, &rules),
+            )
+        } else {
+            (0, 0)
+        };
+        let granaries = if rules.granaries.enabled {
+            rules.granaries.starting_granaries
+        } else {
+            0
+        };
+        // The classic listing opens with a report of an imaginary first year
+        let (grain_eaten_by_rats, new_citizens) =
+            if rules.is_classic() { (200, 5) } else { (0, 0) };
 
         Self {
             year: 1,
-            population: 100,
-            grain: 2800,
-            land: 1000,
+            population: rules.starting_population,
+            grain: rules.starting_grain,
+            land: rules.starting_land,
             land_price: initial_land_price,
+            silver,
+            grain_price,
+            tax_rate: 0,
+            taxes_collected: 0,
+            unrest: 0,
+            granaries,
+            canals: 0,
+            walls: 0,
+            temples: 0,
+            projects: Vec::new(),
             harvest_yield: 3,
-            grain_eaten_by_rats: 0,
-            new_citizens: 0,
+            grain_eaten_by_rats,
+            grain_spoiled: 0,
+            grain_raided: 0,
+            upkeep_paid: 0,
+            new_citizens,
             deaths_starvation: 0,
             deaths_plague: 0,
             total_deaths: 0,
             grain_harvested: 0,
             acres_planted: 0,
+            grain_fed: 0,
+            acres_bought: 0,
+            acres_sold: 0,
+            grain_bought: 0,
+            grain_sold: 0,
+            start_grain: rules.starting_grain,
+            start_land: rules.starting_land,
+            start_population: rules.starting_population,
+            start_silver: silver,
+            history: Vec::new(),
+            rules,
             rng,
             current_phase: GamePhase::Splash,
             unlimited_mode: unlimited,
@@ -63,33 +166,126 @@ impl GameState {
 
     pub fn advance_year(&mut self) {
         self.year += 1;
-        self.land_price = self.rng.gen_range(17..=26);
-        self.current_phase = GamePhase::LandTransaction;
+        self.land_price = self.next_land_price();
+        if self.rules.uses_silver() {
+            self.grain_price = self.next_grain_price();
+        }
+        self.current_phase = self.first_decision_phase();
 
         // Reset per-year tracking variables
+        self.taxes_collected = 0;
         self.harvest_yield = 0;
         self.grain_eaten_by_rats = 0;
+        self.grain_spoiled = 0;
+        self.grain_raided = 0;
+        self.upkeep_paid = 0;
         self.new_citizens = 0;
         self.deaths_starvation = 0;
         self.deaths_plague = 0;
         self.grain_harvested = 0;
         self.acres_planted = 0;
+        self.grain_fed = 0;
+        self.acres_bought = 0;
+        self.acres_sold = 0;
+        self.grain_bought = 0;
+        self.grain_sold = 0;
+
+        self.start_grain = self.grain;
+        self.start_land = self.land;
+        self.start_population = self.population;
+        self.start_silver = self.silver;
+    }
+
+    /// The decision each year opens with.
+    pub fn first_decision_phase(&self) -> GamePhase {
+        if self.rules.taxation.enabled {
+            GamePhase::Taxation
+        } else {
+            GamePhase::LandTransaction
+        }
+    }
+
+    /// The decision asked for after the current one, or `None` once the
+    /// year is ready to be resolved. Classic rules feed before planting.
+    pub fn next_decision_phase(&self) -> Option<GamePhase> {
+        let feed_first = self.rules.is_classic();
+        match self.current_phase {
+            GamePhase::Taxation => Some(GamePhase::LandTransaction),
+            GamePhase::LandTransaction if self.rules.builds() => Some(GamePhase::Construction),
+            GamePhase::Construction => Some(GamePhase::Planting),
+            GamePhase::LandTransaction if feed_first => Some(GamePhase::Feeding),
+            GamePhase::LandTransaction => Some(GamePhase::Planting),
+            GamePhase::Feeding if feed_first => Some(GamePhase::Planting),
+            GamePhase::Planting if !feed_first => Some(GamePhase::Feeding),
+            _ => None,
+        }
     }
 
     pub fn max_plantable_acres(&self) -> u32 {
-        let by_population = self.population * 10;
-        let by_grain = self.grain;
+        let by_population = self.workable_acres();
+        let by_grain = self.rules.acres_sown_with(self.grain);
         let by_land = self.land;
 
         by_population.min(by_grain).min(by_land)
     }
 
+    /// Acres the current population, less those at work on buildings, is
+    /// able to till.
+    pub fn workable_acres(&self) -> u32 {
+        let acres = self.population.saturating_sub(self.builders()) * self.rules.acres_per_worker;
+        if self.rules.is_classic() {
+            // The listing only lets the people tend strictly fewer acres
+            acres.saturating_sub(1)
+        } else {
+            acres
+        }
+    }
+
+    /// Most acres the treasury, or the stores without the silver economy,
+    /// can buy at this year's price.
+    pub fn affordable_acres(&self) -> u32 {
+        let funds = if self.rules.uses_silver() {
+            self.silver
+        } else {
+            self.grain
+        };
+        funds.checked_div(self.land_price).unwrap_or(0)
+    }
+
+    /// Shekels paid for `bushels` of grain, rounded up.
+    pub fn grain_cost(&self, bushels: u32) -> u64 {
+        (bushels as u64 * self.grain_price as u64).div_ceil(100)
+    }
+
+    /// Shekels fetched by selling `bushels` of grain, rounded down.
+    pub fn grain_value(&self, bushels: u32) -> u64 {
+        bushels as u64 * self.grain_price as u64 / 100
+    }
+
+    /// Most bushels `silver` shekels can buy at this year's price.
+    pub fn grain_for_silver(&self, silver: u32) -> u32 {
+        (silver as u64 * 100)
+            .checked_div(self.grain_price as u64)
+            .map_or(0, |bushels| bushels.min(u32::MAX as u64) as u32)
+    }
+
     pub fn grain_needed_for_feeding(&self) -> u32 {
-        self.population * 20
+        self.population * self.rules.bushels_per_person
+    }
+
+    /// People left to starve if `bushels` are handed out this year.
+    pub fn starvation_for(&self, bushels: u32) -> u32 {
+        self.population
+            .saturating_sub(bushels / self.rules.bushels_per_person)
     }
 
     pub fn is_game_over(&self) -> bool {
-        if !self.unlimited_mode && self.year > 10 {
+        let last_year = if self.rules.is_classic() {
+            self.year >= self.rules.years
+        } else {
+            self.year > self.rules.years
+        };
+        if !self.unlimited_mode && last_year {
             return true;
         }
 
@@ -97,16 +293,56 @@ impl GameState {
             return true;
         }
 
-        if self.deaths_starvation > 0
-            && self.deaths_starvation * 100 / (self.population + self.deaths_starvation) > 45
-        {
-            return true;
+        self.was_impeached() || self.was_overthrown()
+    }
+
+    /// Whether more of the people starved this year than the rules allow.
+    pub fn was_impeached(&self) -> bool {
+        if self.rules.is_classic() {
+            // Measured against the people alive before the famine, as the
+            // listing does
+            return self.history.last().is_some_and(|record| {
+                self.rules
+                    .impeaches(record.deaths_starvation, record.start_population)
+            });
         }
 
-        false
+        self.rules.impeaches(
+            self.deaths_starvation,
+            self.population + self.deaths_starvation,
+        )
     }
 
     pub fn save_year_summary(&mut self) {
-        // This method is kept for compatibility but no longer stores history
+        self.history.push(YearRecord {
+            year: self.year,
+            start_grain: self.start_grain,
+            end_grain: self.grain,
+            start_land: self.start_land,
+            end_land: self.land,
+            start_population: self.start_population,
+            end_population: self.population,
+            land_price: self.land_price,
+            start_silver: self.start_silver,
+            end_silver: self.silver,
+            grain_price: self.grain_price,
+            tax_rate: self.tax_rate,
+            taxes_collected: self.taxes_collected,
+            unrest: self.unrest,
+            granaries: self.granaries,
+            canals: self.canals,
+            walls: self.walls,
+            temples: self.temples,
+            grain_spoiled: self.grain_spoiled,
+            grain_raided: self.grain_raided,
+            upkeep_paid: self.upkeep_paid,
+            acres_planted: self.acres_planted,
+            grain_fed: self.grain_fed,
+            harvest_yield: self.harvest_yield,
+            grain_eaten_by_rats: self.grain_eaten_by_rats,
+            new_citizens: self.new_citizens,
+            deaths_starvation: self.deaths_starvation,
+            deaths_plague: self.deaths_plague,
+        });
     }
 }
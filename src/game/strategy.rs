@@ -0,0 +1,308 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::construction::{Building, Works};
+use crate::game::engine::YearDecisions;
+use crate::game::state::GameState;
+
+/// A ruler that decides a whole year at once from the state at its start.
+pub trait Strategy {
+    fn name(&self) -> &str;
+
+    fn decide(&mut self, state: &GameState) -> YearDecisions;
+}
+
+/// Names accepted by [`builtin`].
+pub const BUILTIN_STRATEGIES: [&str; 4] =
+    ["feed-everyone", "max-planting", "speculator", "optimal"];
+
+pub fn builtin(name: &str) -> Option<Box<dyn Strategy>> {
+    match name {
+        "feed-everyone" => Some(Box::new(FeedEveryone)),
+        "max-planting" => Some(Box::new(MaxPlanting)),
+        "speculator" => Some(Box::new(Speculator)),
+        "optimal" => Some(Box::new(Optimal)),
+        _ => None,
+    }
+}
+
+/// Feeds every subject in full, then plants with whatever grain is left.
+pub struct FeedEveryone;
+
+impl Strategy for FeedEveryone {
+    fn name(&self) -> &str {
+        "feed-everyone"
+    }
+
+    fn decide(&mut self, state: &GameState) -> YearDecisions {
+        feed_then_plant(state, 0, 0)
+    }
+}
+
+/// Sows every acre it can, then feeds the people with the rest.
+pub struct MaxPlanting;
+
+impl Strategy for MaxPlanting {
+    fn name(&self) -> &str {
+        "max-planting"
+    }
+
+    fn decide(&mut self, state: &GameState) -> YearDecisions {
+        let plant = state.max_plantable_acres();
+        let feed = state
+            .grain_needed_for_feeding()
+            .min(state.grain - state.rules.seed_for(plant));
+
+        YearDecisions {
+            plant,
+            feed,
+            ..YearDecisions::default()
+        }
+    }
+}
+
+/// Buys all the land it can afford when it is cheap and sells every acre
+/// its people cannot work when it is dear.
+pub struct Speculator;
+
+impl Strategy for Speculator {
+    fn name(&self) -> &str {
+        "speculator"
+    }
+
+    fn decide(&mut self, state: &GameState) -> YearDecisions {
+        let workable = state.workable_acres();
+        // The bottom quarter of the price range is cheap, the top third dear
+        let cheap = state.land_price <= state.rules.land_price_at(1, 4);
+        let dear = state.land_price >= state.rules.land_price_at(2, 3);
+
+        let land = if cheap && state.rules.uses_silver() {
+            state.affordable_acres() as i32
+        } else if cheap {
+            let seed = state.rules.seed_for(workable.min(state.land));
+            let reserve = state.grain_needed_for_feeding() + seed;
+            (state.grain.saturating_sub(reserve) / state.land_price) as i32
+        } else if dear {
+            -(state.land.saturating_sub(workable) as i32)
+        } else {
+            0
+        };
+
+        if state.rules.uses_silver() {
+            let seed = state.rules.seed_for(workable.min(state.land));
+            let needed = state.grain_needed_for_feeding() + seed;
+            return feed_then_plant(state, land, grain_to_buy(state, needed, land));
+        }
+        feed_then_plant(state, land, 0)
+    }
+}
+
+/// The classic advice: keep ten acres for every subject, feed everyone in
+/// full, plant everything the people can work and sell land only to stave
+/// off famine or when the surplus fetches a good price.
+pub struct Optimal;
+
+impl Strategy for Optimal {
+    fn name(&self) -> &str {
+        "optimal"
+    }
+
+    fn decide(&mut self, state: &GameState) -> YearDecisions {
+        let need = state.grain_needed_for_feeding();
+        let target = state.workable_acres();
+        let seed = state.rules.seed_for(target.min(state.land));
+        if state.rules.uses_silver() {
+            return optimal_with_silver(state, need + seed, target);
+        }
+
+        let land = if state.grain < need + seed {
+            // Sell just enough land to feed and sow
+            let deficit = need + seed - state.grain;
+            -(deficit.div_ceil(state.land_price).min(state.land) as i32)
+        } else if state.land < target && buys_at(state, state.land_price) {
+            let budget = state.grain - need - seed;
+            let affordable = budget / (state.land_price + 1);
+            affordable.min(target - state.land) as i32
+        } else if state.land > target && sells_at(state, state.land_price) {
+            -((state.land - target) as i32)
+        } else {
+            0
+        };
+
+        feed_then_plant(state, land, 0)
+    }
+}
+
+/// The same advice when land is paid for in silver: land is bought and
+/// sold with the treasury, which buys whatever grain the year needs.
+fn optimal_with_silver(state: &GameState, needed: u32, target: u32) -> YearDecisions {
+    let price = state.land_price;
+    let deficit = needed.saturating_sub(state.grain);
+    let cost = state.grain_cost(deficit);
+
+    let land = if cost > state.silver as u64 {
+        // Sell just enough land to buy the grain
+        let shortfall = (cost - state.silver as u64) as u32;
+        -(shortfall.div_ceil(price).min(state.land) as i32)
+    } else if state.land < target && buys_at(state, price) {
+        let budget = state.silver - cost as u32;
+        (budget / (price + 1)).min(target - state.land) as i32
+    } else if state.land > target && sells_at(state, price) {
+        -((state.land - target) as i32)
+    } else {
+        0
+    };
+
+    feed_then_plant(state, land, grain_to_buy(state, needed, land))
+}
+
+/// Whether land is cheap enough for the optimal ruler to buy: no dearer
+/// than the middle of the price range.
+fn buys_at(state: &GameState, price: u32) -> bool {
+    price <= state.rules.land_price_at(1, 2)
+}
+
+/// Whether surplus land fetches enough for the optimal ruler to sell: at
+/// least a third of the way up the price range.
+fn sells_at(state: &GameState, price: u32) -> bool {
+    price >= state.rules.land_price_at(1, 3)
+}
+
+/// Bushels to buy with the silver left once `land` is traded, so the stores
+/// hold `needed`.
+fn grain_to_buy(state: &GameState, needed: u32, land: i32) -> i32 {
+    let silver = if land >= 0 {
+        state.silver.saturating_sub(land as u32 * state.land_price)
+    } else {
+        state.silver + land.unsigned_abs() * state.land_price
+    };
+    let deficit = needed.saturating_sub(state.grain);
+    deficit.min(state.grain_for_silver(silver)) as i32
+}
+
+/// A fixed way of ruling: feed first, then plant with what is left.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Policy {
+    /// Bushels handed to each person every year.
+    pub feed_per_person: u32,
+    /// Share of the plantable acres to sow, in percent.
+    pub plant_percent: u32,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self {
+            feed_per_person: 20,
+            plant_percent: 100,
+        }
+    }
+}
+
+impl Strategy for Policy {
+    fn name(&self) -> &str {
+        "policy"
+    }
+
+    fn decide(&mut self, state: &GameState) -> YearDecisions {
+        let feed = state
+            .population
+            .saturating_mul(self.feed_per_person)
+            .min(state.grain);
+        let plantable = state
+            .workable_acres()
+            .min(state.land)
+            .min(state.rules.acres_sown_with(state.grain - feed));
+
+        YearDecisions {
+            plant: (plantable as u64 * self.plant_percent.min(100) as u64 / 100) as u32,
+            feed,
+            ..YearDecisions::default()
+        }
+    }
+}
+
+/// Trades `land` and `grain`, begins what works the surplus allows, feeds
+/// everyone the stores allow and plants the rest.
+fn feed_then_plant(state: &GameState, land: i32, grain: i32) -> YearDecisions {
+    // Levy what the people bear without growing restless
+    let tax = if state.rules.taxation.enabled {
+        state.rules.taxation.tolerated_rate
+    } else {
+        0
+    };
+    let trades = YearDecisions {
+        land,
+        grain,
+        tax,
+        ..YearDecisions::default()
+    };
+    let traded = state.project(&trades);
+    let works = works_to_build(state, traded.grain_remaining, traded.land);
+    let cost = state.rules.works_cost(&works).unwrap_or(u32::MAX);
+    let stores = traded.grain_remaining.saturating_sub(cost);
+    let builders = state.rules.works_labor(&works).unwrap_or(u32::MAX);
+
+    let feed = state.grain_needed_for_feeding().min(stores);
+    let plant = state
+        .workable_acres()
+        .saturating_sub(builders.saturating_mul(state.rules.acres_per_worker))
+        .min(traded.land)
+        .min(state.rules.acres_sown_with(stores - feed));
+
+    YearDecisions {
+        land,
+        grain,
+        plant,
+        feed,
+        tax,
+        granaries: works.granaries,
+        canals: works.canals,
+        walls: works.walls,
+        temples: works.temples,
+    }
+}
+
+/// A granary when an average harvest on top of the grain left after
+/// feeding and sowing would overflow the granaries. While nothing else is
+/// under way, also the least built of the other works, if the surplus pays
+/// for it and its upkeep and it stands before the reign ends.
+fn works_to_build(state: &GameState, stores: u32, land: u32) -> Works {
+    let rules = &state.rules;
+    let mut works = Works::default();
+    if !rules.builds() {
+        return works;
+    }
+
+    let sown = state.workable_acres().min(land);
+    let reserve = state.grain_needed_for_feeding() + rules.seed_for(sown);
+    let mut surplus = stores.saturating_sub(reserve);
+
+    let granary = rules.project(Building::Granary);
+    let usual_yield = (rules.harvest_yield_min + rules.harvest_yield_max) / 2;
+    if state.affordable(Building::Granary) > 0
+        && surplus >= granary.grain_cost
+        && surplus - granary.grain_cost + sown * usual_yield > state.granary_capacity()
+    {
+        works.granaries = 1;
+        surplus -= granary.grain_cost;
+    }
+
+    if !state.projects.is_empty() {
+        return works;
+    }
+    let next = [Building::Canal, Building::Wall, Building::Temple]
+        .into_iter()
+        .filter(|&building| state.affordable(building) > 0)
+        .min_by_key(|&building| state.built(building));
+    if let Some(building) = next {
+        let project = rules.project(building);
+        let in_time = state.unlimited_mode || state.year + project.years <= rules.years;
+        let hands = state
+            .builders()
+            .saturating_add(rules.works_labor(&works).unwrap_or(u32::MAX))
+            .saturating_add(project.workers);
+        if in_time && hands <= state.population && surplus >= project.grain_cost + state.upkeep() {
+            *works.count_mut(building) += 1;
+        }
+    }
+    works
+}
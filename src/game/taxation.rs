@@ -0,0 +1,75 @@
+use crate::game::actions::ActionResult;
+use crate::game::events::YearEvent;
+use crate::game::state::GameState;
+
+impl GameState {
+    /// Levies `rate` percent on the people for the year and collects it at
+    /// once, in silver under the silver economy and in grain otherwise.
+    pub(crate) fn set_tax_rate(&mut self, rate: u32) -> ActionResult {
+        let taxation = &self.rules.taxation;
+        let max_rate = if taxation.enabled {
+            taxation.max_rate
+        } else {
+            0
+        };
+        if rate > max_rate {
+            return ActionResult::TaxTooHigh;
+        }
+
+        let collected = self.taxes_at(rate);
+        if self.rules.uses_silver() {
+            self.silver += collected;
+        } else {
+            self.grain += collected;
+        }
+        self.tax_rate = rate;
+        self.taxes_collected = collected;
+        ActionResult::Success
+    }
+
+    /// Bushels, or shekels under the silver economy, that `rate` percent
+    /// raises from the people this year.
+    pub fn taxes_at(&self, rate: u32) -> u32 {
+        self.population * self.rules.taxation.yield_per_person * rate / 100
+    }
+
+    /// Share of the year's newcomers kept away by the taxes, in percent.
+    pub fn immigration_deterred(&self) -> u32 {
+        if !self.rules.taxation.enabled {
+            return 0;
+        }
+        (self.tax_rate * self.rules.taxation.immigration_penalty).min(100)
+    }
+
+    /// Taxes above what the people tolerate stir them up; lighter years
+    /// let them settle.
+    pub(crate) fn process_unrest(&mut self) -> YearEvent {
+        let taxation = &self.rules.taxation;
+        let before = self.unrest;
+        self.unrest = if self.tax_rate > taxation.tolerated_rate {
+            let stirred = (self.tax_rate - taxation.tolerated_rate) * taxation.unrest_per_point;
+            (self.unrest + stirred).min(taxation.revolt_unrest)
+        } else {
+            self.unrest.saturating_sub(taxation.unrest_decay)
+        };
+
+        YearEvent::Unrest {
+            level: self.unrest,
+            change: self.unrest as i32 - before as i32,
+        }
+    }
+
+    /// Whether the people rose against the ruler this year.
+    pub fn was_overthrown(&self) -> bool {
+        self.rules.taxation.enabled && self.unrest >= self.rules.taxation.revolt_unrest
+    }
+
+    /// Unrest averaged over the years resolved so far.
+    pub fn mean_unrest(&self) -> f32 {
+        if self.history.is_empty() {
+            return 0.0;
+        }
+        let total: u32 = self.history.iter().map(|record| record.unrest).sum();
+        total as f32 / self.history.len() as f32
+    }
+}
@@ -0,0 +1,437 @@
+use crate::app::App;
+use hammurabi::game::{GamePhase, GameState, Rules};
+
+/// A titled part of the help overlay.
+pub struct HelpSection {
+    pub title: &'static str,
+    pub lines: Vec<String>,
+}
+
+/// Every key the game answers to, with what it does.
+const KEYS: &[(&str, &str)] = &[
+    ("ENTER", "GIVE THINE ANSWER, OR MOVE ON"),
+    (
+        "BACKSPACE",
+        "ERASE, OR TAKE BACK THE LAST ANSWER ON AN EMPTY LINE",
+    ),
+    ("DELETE", "ERASE UNDER THE CURSOR"),
+    ("LEFT/RIGHT", "MOVE THE CURSOR, OR THE FIELD WHILE PLANNING"),
+    ("HOME/END", "JUMP TO EITHER END OF THE ANSWER"),
+    ("UP/DOWN", "RECALL EARLIER ANSWERS, OR SCROLL THE CHRONICLE"),
+    ("PAGE UP/DOWN", "SCROLL THE CHRONICLE"),
+    ("TAB", "SHOW OR HIDE THE CHARTS"),
+    ("F1 OR ?", "THIS COUNSEL"),
+    ("F2", "ASK THE ADVISOR"),
+    ("F3", "LET THE ADVISOR RULE IN THY STEAD"),
+    ("F4", "PLAN A YEAR WITHOUT MARKET OR BUILDING ON ONE SCREEN"),
+    ("CTRL+S", "SAVE THY REIGN"),
+    ("SPACE", "STEP A REPLAY ONE DECISION ON"),
+    ("A", "LET A REPLAY RUN ON ITS OWN"),
+    ("ESC OR CTRL+C", "DEPART"),
+];
+
+/// Help for the decision at hand followed by the laws, chances and
+/// judgement of the active rules and the keys. Every number is read from
+/// the rules so the text holds for any rules file.
+pub fn help_sections(app: &App) -> Vec<HelpSection> {
+    let game = &app.game;
+    vec![
+        HelpSection {
+            title: "THE MATTER BEFORE THEE",
+            lines: current_decision(game, app.trading_grain()),
+        },
+        HelpSection {
+            title: "THE LAWS OF THE LAND",
+            lines: constraints(&game.rules),
+        },
+        HelpSection {
+            title: "THE WHIMS OF THE GODS",
+            lines: event_chances(&game.rules),
+        },
+        HelpSection {
+            title: "THE JUDGEMENT OF HISTORY",
+            lines: scoring(&game.rules, game.unlimited_mode),
+        },
+        HelpSection {
+            title: "THE KEYS OF THY OFFICE",
+            lines: KEYS
+                .iter()
+                .map(|(key, action)| format!("{:<14}{}", key, action))
+                .collect(),
+        },
+    ]
+}
+
+fn current_decision(game: &GameState, trading_grain: bool) -> Vec<String> {
+    let rules = &game.rules;
+    let mut lines = match game.current_phase {
+        GamePhase::Splash | GamePhase::Instructions => {
+            let term = if game.unlimited_mode {
+                "FOR AS LONG AS THOU CANST KEEP THY THRONE".to_string()
+            } else {
+                format!("FOR A TERM OF {} YEARS", rules.years)
+            };
+            vec![
+                format!("THOU RULEST ANCIENT SUMERIA {}.", term),
+                "EACH YEAR THOU TRADEST LAND, SOWEST FIELDS AND FEEDEST THY PEOPLE;".to_string(),
+                "THEN THE HARVEST IS GATHERED AND THE GODS HAVE THEIR SAY.".to_string(),
+            ]
+        }
+        GamePhase::Taxation => {
+            let taxation = &rules.taxation;
+            vec![
+                format!(
+                    "WHAT TAX TO LEVY THIS YEAR, IN PERCENT, UP TO {}. LAST YEAR THOU TOOKEST {}.",
+                    taxation.max_rate, game.tax_rate
+                ),
+                format!(
+                    "AT {}% THY {} SUBJECTS WOULD PAY {} {} AT ONCE.",
+                    taxation.max_rate,
+                    game.population,
+                    game.taxes_at(taxation.max_rate),
+                    rules.land_currency()
+                ),
+                format!(
+                    "EVERY PERCENT KEEPS {}% OF NEWCOMERS AWAY; ABOVE {}% THY PEOPLE GROW RESTLESS.",
+                    taxation.immigration_penalty, taxation.tolerated_rate
+                ),
+                format!(
+                    "UNREST STANDS AT {}. AT {} THEY RISE AGAINST THEE; A RESTLESS REIGN IS JUDGED HARSHLY.",
+                    game.unrest, taxation.revolt_unrest
+                ),
+            ]
+        }
+        GamePhase::LandTransaction if trading_grain => vec![
+            "HOW MANY BUSHELS TO BUY WITH SILVER, OR TO SELL WITH A NEGATIVE NUMBER.".to_string(),
+            format!(
+                "A HUNDRED BUSHELS FETCH {} SHEKELS THIS YEAR, BETWEEN {} AND {} IN ANY YEAR.",
+                game.grain_price, rules.economy.grain_price_min, rules.economy.grain_price_max
+            ),
+            format!(
+                "THY {} SHEKELS BUY AT MOST {} BUSHELS; THOU CANST SELL UP TO {}.",
+                game.silver,
+                game.grain_for_silver(game.silver),
+                game.grain
+            ),
+            "GRAIN IS CHEAP AFTER A RICH HARVEST AND DEAR WHEN THOU BUYEST MUCH OF IT.".to_string(),
+        ],
+        GamePhase::LandTransaction => {
+            let mut lines = vec![
+                if rules.is_classic() {
+                    "HOW MANY ACRES TO BUY, THEN, IF NONE, HOW MANY TO SELL.".to_string()
+                } else {
+                    "HOW MANY ACRES TO BUY, OR TO SELL WITH A NEGATIVE NUMBER.".to_string()
+                },
+                format!(
+                    "LAND TRADES AT {} {} AN ACRE THIS YEAR, BETWEEN {} AND {} IN ANY YEAR.",
+                    game.land_price,
+                    rules.land_currency(),
+                    rules.land_price_min,
+                    rules.land_price_max
+                ),
+                format!(
+                    "THY {} {} BUY AT MOST {} ACRES; THOU CANST SELL UP TO {}.",
+                    if rules.uses_silver() {
+                        game.silver
+                    } else {
+                        game.grain
+                    },
+                    rules.land_currency(),
+                    game.affordable_acres(),
+                    game.land
+                ),
+            ];
+            if rules.is_classic() {
+                lines.push("THE CLASSIC STEWARD WILL NOT SELL THY LAST ACRE.".to_string());
+                lines.push("A NEGATIVE ANSWER TO ANY QUESTION LOSES THEE THY STEWARD.".to_string());
+            } else if rules.market.dynamic {
+                lines.push(
+                    "THE PRICE RISES AFTER A RICH HARVEST, WITH FULL STORES AND WHEN THOU BUYEST,"
+                        .to_string(),
+                );
+                lines.push(
+                    "FALLS WHEN THOU SELLEST, AND EVER DRIFTS BACK TOWARD THE MIDDLE.".to_string(),
+                );
+            }
+            lines
+        }
+        GamePhase::Construction => {
+            let mut lines =
+                vec!["WHAT TO BUILD, AS IN '2 GRANARIES, WALL', OR NOTHING.".to_string()];
+            for building in rules.buildable() {
+                let project = rules.project(building);
+                lines.push(format!(
+                    "{}: {} BUSHELS, {} WORKERS FOR {} {}, {} BUSHELS A YEAR TO KEEP; THOU HAST {}.",
+                    building.name(1),
+                    project.grain_cost,
+                    project.workers,
+                    project.years,
+                    if project.years == 1 { "YEAR" } else { "YEARS" },
+                    project.upkeep,
+                    game.built(building)
+                ));
+            }
+            lines.push(format!(
+                "UNDER WAY: {}, KEEPING {} PEOPLE FROM THE FIELDS.",
+                game.works_under_way(),
+                game.builders()
+            ));
+            if rules.granaries.enabled {
+                let granaries = &rules.granaries;
+                lines.push(format!(
+                    "THY GRANARIES HOLD {} BUSHELS; EACH YEAR {}% OF THE GRAIN BEYOND THAT ROTS.",
+                    game.granary_capacity(),
+                    granaries.spoilage_percent
+                ));
+                lines.push(format!(
+                    "EACH GRANARY SAVES {}% OF WHAT RATS WOULD EAT, UP TO {}%; THINE SAVE {}%.",
+                    granaries.rat_protection,
+                    granaries.max_rat_protection,
+                    game.rat_protection()
+                ));
+            }
+            if rules.construction.enabled {
+                let construction = &rules.construction;
+                lines.push(format!(
+                    "EACH CANAL RAISES THE POOREST HARVEST BY {} AN ACRE; THINE YIELD AT LEAST {}.",
+                    construction.canal_yield,
+                    game.harvest_floor()
+                ));
+                lines.push(format!(
+                    "RAIDERS STRIKE IN {}% OF YEARS; EACH WALL SAVES {}% OF THEIR PLUNDER, UP TO {}%.",
+                    construction.raid_chance,
+                    construction.wall_protection,
+                    construction.max_wall_protection
+                ));
+                lines.push(format!(
+                    "EACH TEMPLE LOWERS THE CHANCE OF PLAGUE BY {} POINTS; IT STANDS AT {}%.",
+                    construction.temple_protection,
+                    game.plague_chance()
+                ));
+            }
+            lines
+        }
+        GamePhase::Planting => vec![
+            "HOW MANY ACRES TO SOW WITH SEED FOR NEXT YEAR'S HARVEST.".to_string(),
+            format!(
+                "THOU CANST SOW {} ACRES: THY WORKERS TILL {}, THY GRAIN SEEDS {}, THOU OWNEST {}.",
+                game.max_plantable_acres(),
+                game.workable_acres(),
+                rules.acres_sown_with(game.grain),
+                game.land
+            ),
+        ],
+        GamePhase::Feeding => vec![
+            "HOW MANY BUSHELS TO HAND OUT TO THY PEOPLE.".to_string(),
+            format!(
+                "THY {} SUBJECTS NEED {} BUSHELS; THY STORES HOLD {}.",
+                game.population,
+                game.grain_needed_for_feeding(),
+                game.grain
+            ),
+            format!(
+                "IF MORE THAN {}% OF THEM STARVE IN ONE YEAR THOU ART IMPEACHED.",
+                rules.impeachment_percent
+            ),
+        ],
+        GamePhase::YearEnd => vec![
+            "THE YEAR IS DONE. PRESS ENTER TO BEGIN THE NEXT.".to_string(),
+            "THE HARVEST, THE RATS, NEWCOMERS AND PLAGUE ARE RECORDED ABOVE.".to_string(),
+        ],
+        GamePhase::GameOver => {
+            vec!["THY REIGN IS OVER. THE CHRONICLE TELLS OF EVERY YEAR OF IT.".to_string()]
+        }
+    };
+
+    if game.current_phase.is_decision() {
+        lines.push(
+            "ANSWERS MAY BE SUMS OF NUMBERS AND THE WORDS MAX, ALL, HALF AND NEED.".to_string(),
+        );
+    }
+    lines
+}
+
+fn constraints(rules: &Rules) -> Vec<String> {
+    let seed = if rules.acres_per_seed_bushel == 1 {
+        "ONE BUSHEL OF SEED SOWS AN ACRE.".to_string()
+    } else {
+        format!(
+            "ONE BUSHEL OF SEED SOWS {} ACRES.",
+            rules.acres_per_seed_bushel
+        )
+    };
+
+    vec![
+        format!(
+            "EACH PERSON EATS {} BUSHELS A YEAR.",
+            rules.bushels_per_person
+        ),
+        format!("EACH PERSON CAN TILL {} ACRES.", rules.acres_per_worker),
+        seed,
+        format!(
+            "EACH SOWN ACRE YIELDS {} TO {} BUSHELS AT HARVEST.",
+            rules.harvest_yield_min, rules.harvest_yield_max
+        ),
+    ]
+}
+
+fn event_chances(rules: &Rules) -> Vec<String> {
+    if rules.is_classic() {
+        return vec![
+            "THE HARVEST AND THE RATS ARE EACH A ROLL OF ONE TO FIVE.".to_string(),
+            format!(
+                "ON AN EVEN ROLL ({}% OF YEARS) RATS EAT A HALF OR A QUARTER OF THE STORES.",
+                rules.rats_chance
+            ),
+            "NEWCOMERS ARRIVE BY A THIRD ROLL, THE LAND AND THE STORES, UNLESS THE".to_string(),
+            "FAMINE IMPEACHES THEE.".to_string(),
+            format!(
+                "PLAGUE KILLS HALF THE CITY IN {}% OF YEARS.",
+                rules.plague_chance
+            ),
+        ];
+    }
+
+    let mut lines = vec![
+        format!(
+            "RATS EAT {}% TO {}% OF THE STORES IN {}% OF YEARS.",
+            rules.rats_damage_min, rules.rats_damage_max, rules.rats_chance
+        ),
+        format!(
+            "PLAGUE KILLS HALF THE CITY IN {}% OF YEARS.",
+            rules.plague_chance
+        ),
+        format!(
+            "NEWCOMERS ARRIVE IN YEARS WITHOUT FAMINE, AT MOST {} AT A TIME.",
+            rules.immigration_cap
+        ),
+    ];
+    if rules.construction.enabled {
+        let construction = &rules.construction;
+        lines.push(format!(
+            "RAIDERS CARRY OFF {}% TO {}% OF THE STORES IN {}% OF YEARS.",
+            construction.raid_loss_min, construction.raid_loss_max, construction.raid_chance
+        ));
+    }
+    lines
+}
+
+fn scoring(rules: &Rules, unlimited: bool) -> Vec<String> {
+    let t = &rules.scoring;
+    let mut lines = vec![
+        if rules.is_classic() {
+            "THY REIGN IS JUDGED BY THE SHARE OF THE PEOPLE STARVED EACH YEAR".to_string()
+        } else {
+            format!(
+                "THY REIGN IS JUDGED BY THE DEATHS AS A SHARE OF {} PEOPLE OVER {} YEARS",
+                rules.starting_population, rules.years
+            )
+        },
+        "AND BY THE ACRES LEFT TO EACH PERSON:".to_string(),
+        format!(
+            "  TERRIBLE: OVER {}% DEAD OR UNDER {} ACRES",
+            t.terrible_death_rate, t.terrible_acres
+        ),
+        format!(
+            "  POOR:     OVER {}% DEAD OR UNDER {} ACRES",
+            t.poor_death_rate, t.poor_acres
+        ),
+        format!(
+            "  FAIR:     OVER {}% DEAD OR UNDER {} ACRES",
+            t.fair_death_rate, t.fair_acres
+        ),
+    ];
+    if t.good_death_rate < t.fair_death_rate || t.good_acres > t.fair_acres {
+        lines.push(format!(
+            "  GOOD:     OVER {}% DEAD OR UNDER {} ACRES",
+            t.good_death_rate, t.good_acres
+        ));
+    }
+    lines.push("  EXCELLENT: ANYTHING BETTER".to_string());
+    if rules.taxation.enabled {
+        lines.push(format!(
+            "A REIGN WHOSE UNREST AVERAGES OVER {} IS JUDGED ONE TIER LOWER.",
+            t.unrest
+        ));
+    }
+    if unlimited {
+        lines.push(
+            "AN UNLIMITED REIGN ENDS ONLY WHEN THY PEOPLE CAST THEE OUT OR PERISH.".to_string(),
+        );
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(app: &App, title: &str) -> Vec<String> {
+        help_sections(app)
+            .into_iter()
+            .find(|section| section.title == title)
+            .unwrap()
+            .lines
+    }
+
+    #[test]
+    fn every_key_is_explained() {
+        let keys = section(
+            &App::new(Some(1), false, Rules::default()),
+            "THE KEYS OF THY OFFICE",
+        );
+        for key in [
+            "ENTER",
+            "BACKSPACE",
+            "DELETE",
+            "LEFT/RIGHT",
+            "HOME/END",
+            "UP/DOWN",
+            "PAGE UP/DOWN",
+            "TAB",
+            "F1 OR ?",
+            "F2",
+            "F3",
+            "F4",
+            "CTRL+S",
+            "SPACE",
+            "A ",
+            "ESC OR CTRL+C",
+        ] {
+            assert!(
+                keys.iter().any(|line| line.starts_with(key)),
+                "{} is not explained",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn the_matter_at_hand_follows_the_phase() {
+        let mut app = App::new(Some(1), false, Rules::default());
+        app.game.current_phase = GamePhase::Feeding;
+        let lines = section(&app, "THE MATTER BEFORE THEE");
+        assert_eq!(lines[0], "HOW MANY BUSHELS TO HAND OUT TO THY PEOPLE.");
+        assert_eq!(
+            lines[1],
+            "THY 100 SUBJECTS NEED 2000 BUSHELS; THY STORES HOLD 2800."
+        );
+
+        app.game.current_phase = GamePhase::YearEnd;
+        let lines = section(&app, "THE MATTER BEFORE THEE");
+        assert_eq!(lines[0], "THE YEAR IS DONE. PRESS ENTER TO BEGIN THE NEXT.");
+    }
+
+    #[test]
+    fn the_laws_are_read_from_the_rules() {
+        let rules = Rules {
+            bushels_per_person: 25,
+            acres_per_seed_bushel: 1,
+            ..Rules::default()
+        };
+        let app = App::new(Some(1), false, rules);
+        let laws = section(&app, "THE LAWS OF THE LAND");
+        assert_eq!(laws[0], "EACH PERSON EATS 25 BUSHELS A YEAR.");
+        assert_eq!(laws[2], "ONE BUSHEL OF SEED SOWS AN ACRE.");
+    }
+}
@@ -0,0 +1,443 @@
+use hammurabi::game::{Building, Works};
+
+/// The answer line: text with a cursor and the answers given before it.
+#[derive(Default)]
+pub struct LineEditor {
+    text: String,
+    /// Byte offset into `text`, which only ever holds ASCII.
+    cursor: usize,
+    history: Vec<String>,
+    /// Index into `history` while an old answer is being recalled.
+    recalled: Option<usize>,
+}
+
+impl LineEditor {
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn set(&mut self, text: String) {
+        self.text = text;
+        self.cursor = self.text.len();
+        self.recalled = None;
+    }
+
+    pub fn clear(&mut self) {
+        self.set(String::new());
+    }
+
+    pub fn insert(&mut self, c: char) {
+        if c.is_ascii() {
+            self.text.insert(self.cursor, c);
+            self.cursor += 1;
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            self.text.remove(self.cursor);
+        }
+    }
+
+    pub fn delete(&mut self) {
+        if self.cursor < self.text.len() {
+            self.text.remove(self.cursor);
+        }
+    }
+
+    pub fn left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.text.len());
+    }
+
+    pub fn home(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn end(&mut self) {
+        self.cursor = self.text.len();
+    }
+
+    /// Remembers the current answer for recall, skipping repeats.
+    pub fn remember(&mut self) {
+        if !self.text.is_empty() && self.history.last() != Some(&self.text) {
+            self.history.push(self.text.clone());
+        }
+        self.recalled = None;
+    }
+
+    /// Steps back through the answers given before.
+    pub fn recall_previous(&mut self) {
+        let index = match self.recalled {
+            Some(index) => index.saturating_sub(1),
+            None if self.history.is_empty() => return,
+            None => self.history.len() - 1,
+        };
+        self.text = self.history[index].clone();
+        self.cursor = self.text.len();
+        self.recalled = Some(index);
+    }
+
+    /// Steps forward again, ending on an empty line.
+    pub fn recall_next(&mut self) {
+        let Some(index) = self.recalled else {
+            return;
+        };
+
+        if index + 1 < self.history.len() {
+            self.text = self.history[index + 1].clone();
+            self.cursor = self.text.len();
+            self.recalled = Some(index + 1);
+        } else {
+            self.clear();
+        }
+    }
+}
+
+/// What the words in an answer stand for at the current decision. `half`
+/// is half of `all`.
+pub struct Keywords {
+    /// The most this decision allows.
+    pub max: i64,
+    /// Everything held of what the decision spends.
+    pub all: i64,
+    /// Bushels that would feed every subject.
+    pub need: i64,
+}
+
+/// Evaluates an answer such as `20*95`, `need+100` or `-(all-1000)` with
+/// whole-number arithmetic.
+pub fn evaluate(expression: &str, keywords: &Keywords) -> Result<i64, String> {
+    let tokens = tokenize(expression)?;
+    let mut parser = Parser {
+        tokens: &tokens,
+        position: 0,
+        keywords,
+    };
+
+    let value = parser.expression()?;
+    match parser.tokens.get(parser.position) {
+        None => Ok(value),
+        Some(token) => Err(format!("THE SCRIBES CANNOT READ '{}'", token)),
+    }
+}
+
+/// Reads building orders such as `2 GRANARIES, WALL` or `NOTHING`. A bare
+/// number orders that many of the only kind in `buildable`.
+pub fn parse_works(orders: &str, buildable: &[Building]) -> Result<Works, String> {
+    let mut works = Works::default();
+    let mut count = None;
+    let words = orders
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|word| !word.is_empty());
+    for word in words {
+        let word = word.to_ascii_uppercase();
+        if word.chars().all(|c| c.is_ascii_digit()) {
+            if count.is_some() {
+                return Err("NAME WHAT THOU WOULDST BUILD".to_string());
+            }
+            let number = word.parse::<u32>().map_err(|_| OVERFLOW.to_string())?;
+            count = Some(number);
+            continue;
+        }
+        if matches!(word.as_str(), "NOTHING" | "NONE" | "AND") {
+            continue;
+        }
+
+        let building = Building::ALL
+            .into_iter()
+            .find(|building| {
+                word == building.name(1)
+                    || word == building.name(2)
+                    || (*building == Building::Canal && word == "IRRIGATION")
+            })
+            .ok_or_else(|| format!("THY BUILDERS KNOW NOT WHAT '{}' IS", word))?;
+        order(&mut works, building, count.take().unwrap_or(1))?;
+    }
+
+    match (count, buildable) {
+        (None | Some(0), _) => {}
+        (Some(number), [only]) => order(&mut works, *only, number)?,
+        (Some(_), _) => return Err("NAME WHAT THOU WOULDST BUILD".to_string()),
+    }
+    Ok(works)
+}
+
+fn order(works: &mut Works, building: Building, count: u32) -> Result<(), String> {
+    let ordered = works.count_mut(building);
+    *ordered = ordered.checked_add(count).ok_or(OVERFLOW)?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(i64),
+    Word(String),
+    Symbol(char),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Number(n) => write!(f, "{}", n),
+            Token::Word(word) => write!(f, "{}", word.to_uppercase()),
+            Token::Symbol(c) => write!(f, "{}", c),
+        }
+    }
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() {
+            let mut number: i64 = 0;
+            while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+                number = number
+                    .checked_mul(10)
+                    .and_then(|n| n.checked_add(digit as i64))
+                    .ok_or(OVERFLOW)?;
+                chars.next();
+            }
+            tokens.push(Token::Number(number));
+        } else if c.is_ascii_alphabetic() {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek().filter(|c| c.is_ascii_alphabetic()) {
+                word.push(c.to_ascii_lowercase());
+                chars.next();
+            }
+            tokens.push(Token::Word(word));
+        } else if "+-*/()".contains(c) {
+            tokens.push(Token::Symbol(c));
+            chars.next();
+        } else {
+            return Err(format!("THE SCRIBES CANNOT READ '{}'", c));
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    keywords: &'a Keywords,
+}
+
+impl Parser<'_> {
+    fn eat(&mut self, symbol: char) -> bool {
+        if self.tokens.get(self.position) == Some(&Token::Symbol(symbol)) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expression(&mut self) -> Result<i64, String> {
+        let mut value = self.term()?;
+        loop {
+            if self.eat('+') {
+                value = value.checked_add(self.term()?).ok_or(OVERFLOW)?;
+            } else if self.eat('-') {
+                value = value.checked_sub(self.term()?).ok_or(OVERFLOW)?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<i64, String> {
+        let mut value = self.factor()?;
+        loop {
+            if self.eat('*') {
+                value = value.checked_mul(self.factor()?).ok_or(OVERFLOW)?;
+            } else if self.eat('/') {
+                let divisor = self.factor()?;
+                if divisor == 0 {
+                    return Err("EVEN THE GODS CANNOT DIVIDE BY NOUGHT".to_string());
+                }
+                value = value.checked_div(divisor).ok_or(OVERFLOW)?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn factor(&mut self) -> Result<i64, String> {
+        if self.eat('-') {
+            return self.factor()?.checked_neg().ok_or(OVERFLOW.to_string());
+        }
+        if self.eat('(') {
+            let value = self.expression()?;
+            if !self.eat(')') {
+                return Err("THY PARENTHESES ARE UNBALANCED".to_string());
+            }
+            return Ok(value);
+        }
+
+        let token = self
+            .tokens
+            .get(self.position)
+            .ok_or("THINE ANSWER ENDS TOO SOON")?;
+        self.position += 1;
+        match token {
+            Token::Number(n) => Ok(*n),
+            Token::Word(word) => match word.as_str() {
+                "max" => Ok(self.keywords.max),
+                "all" => Ok(self.keywords.all),
+                "need" => Ok(self.keywords.need),
+                "half" => Ok(self.keywords.all / 2),
+                _ => Err(format!("THE SCRIBES KNOW NOT THE WORD '{}'", token)),
+            },
+            Token::Symbol(_) => Err(format!("THE SCRIBES CANNOT READ '{}'", token)),
+        }
+    }
+}
+
+pub(crate) const OVERFLOW: &str = "THAT NUMBER IS BEYOND ALL RECKONING";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keywords() -> Keywords {
+        Keywords {
+            max: 1000,
+            all: 2801,
+            need: 2000,
+        }
+    }
+
+    fn eval(expression: &str) -> Result<i64, String> {
+        evaluate(expression, &keywords())
+    }
+
+    #[test]
+    fn multiplication_binds_tighter_than_addition() {
+        assert_eq!(eval("2+3*4"), Ok(14));
+        assert_eq!(eval("(2+3)*4"), Ok(20));
+        assert_eq!(eval("20 - 8 / 2 - 1"), Ok(15));
+        assert_eq!(eval("-(2-5)*-3"), Ok(-9));
+    }
+
+    #[test]
+    fn words_stand_for_the_decision_at_hand() {
+        assert_eq!(eval("max"), Ok(1000));
+        assert_eq!(eval("ALL"), Ok(2801));
+        assert_eq!(eval("half"), Ok(1400));
+        assert_eq!(eval("need+100"), Ok(2100));
+        assert_eq!(eval("-(all-1000)"), Ok(-1801));
+        assert!(eval("some").unwrap_err().contains("'SOME'"));
+    }
+
+    #[test]
+    fn numbers_beyond_reckoning_are_refused() {
+        for expression in [
+            "9223372036854775808",
+            "9223372036854775807+1",
+            "-9223372036854775807-2",
+            "4294967296*4294967296",
+            "-(-9223372036854775807-1)",
+            "(-9223372036854775807-1)/-1",
+        ] {
+            assert_eq!(
+                eval(expression),
+                Err(OVERFLOW.to_string()),
+                "{}",
+                expression
+            );
+        }
+    }
+
+    #[test]
+    fn division_by_nought_is_refused() {
+        for expression in ["1/0", "all/(need-2000)"] {
+            assert_eq!(
+                eval(expression),
+                Err("EVEN THE GODS CANNOT DIVIDE BY NOUGHT".to_string())
+            );
+        }
+        assert_eq!(eval("7/2"), Ok(3));
+    }
+
+    #[test]
+    fn malformed_answers_are_refused() {
+        assert!(eval("(1+2").is_err());
+        assert!(eval("1+").is_err());
+        assert!(eval("1 2").is_err());
+        assert!(eval("1%2").is_err());
+    }
+
+    #[test]
+    fn the_cursor_edits_mid_line() {
+        let mut line = LineEditor::default();
+        for c in "1200".chars() {
+            line.insert(c);
+        }
+        line.left();
+        line.left();
+        line.backspace();
+        line.insert('5');
+        assert_eq!((line.as_str(), line.cursor()), ("1500", 2));
+
+        line.home();
+        line.left();
+        line.delete();
+        line.insert('2');
+        line.end();
+        line.right();
+        assert_eq!((line.as_str(), line.cursor()), ("2500", 4));
+
+        line.insert('é');
+        assert_eq!(line.as_str(), "2500");
+    }
+
+    #[test]
+    fn old_answers_are_recalled_in_turn() {
+        let mut line = LineEditor::default();
+        for answer in ["10", "20", "20", ""] {
+            line.set(answer.to_string());
+            line.remember();
+        }
+
+        line.recall_previous();
+        assert_eq!(line.as_str(), "20");
+        line.recall_previous();
+        assert_eq!(line.as_str(), "10");
+        line.recall_previous();
+        assert_eq!(line.as_str(), "10");
+        line.recall_next();
+        assert_eq!(line.as_str(), "20");
+        line.recall_next();
+        assert!(line.is_empty());
+    }
+
+    #[test]
+    fn building_orders_beyond_reckoning_are_refused() {
+        let buildable = [Building::Granary];
+        let works = parse_works("4000000000 granaries", &buildable).unwrap();
+        assert_eq!(works.granaries, 4_000_000_000);
+        for orders in [
+            "3000000000 granaries, 3000000000 granaries",
+            "granary 4294967295",
+        ] {
+            assert_eq!(parse_works(orders, &buildable), Err(OVERFLOW.to_string()));
+        }
+    }
+}
@@ -0,0 +1,26 @@
+//! Headless engine for the classic Hammurabi resource management game.
+//!
+//! The [`game`] module contains everything needed to run a reign without a
+//! terminal: [`Game`] accepts a full year's decisions and returns a
+//! [`YearReport`] describing what happened.
+//!
+//! ```
+//! use hammurabi::{Game, YearDecisions};
+//!
+//! let mut game = Game::new(Some(42), false);
+//! let report = game
+//!     .play_year(&YearDecisions {
+//!         plant: 500,
+//!         feed: 2000,
+//!         ..YearDecisions::default()
+//!     })
+//!     .unwrap();
+//! assert_eq!(report.year, 1);
+//! ```
+
+pub mod game;
+
+pub use game::{
+    evaluate_performance, ActionResult, Game, GameAction, GamePhase, GameState, Rejection, Replay,
+    Score, YearDecisions, YearError, YearEvent, YearRecord, YearReport,
+};
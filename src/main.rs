@@ -1,25 +1,47 @@
 mod app;
+mod config;
 mod event;
-mod game;
+mod help;
+mod input;
 mod messages;
+mod plain;
+mod planner;
+mod protocol;
+mod replay;
+mod save;
+mod theme;
 mod ui;
 
+use anyhow::anyhow;
 use anyhow::Result;
-use clap::Parser;
+use clap::{value_parser, Parser, Subcommand, ValueEnum};
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture, KeyCode, KeyModifiers},
+    event::{
+        DisableMouseCapture, EnableMouseCapture, KeyCode, KeyModifiers, MouseButton, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use hammurabi::game::{
+    builtin, simulate, Game, Policy, Replay, Rules, SimulationStats, Strategy, BUILTIN_STRATEGIES,
+};
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
+use std::path::PathBuf;
 
-use crate::app::App;
+use crate::app::{App, FeedingWarnings};
+use crate::config::{default_config_path, Config};
 use crate::event::{Event, EventHandler};
+use crate::replay::Recorder;
+use crate::save::SaveFile;
+use crate::theme::{Theme, THEME_NAMES};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Random seed for deterministic gameplay
     #[arg(short, long)]
     seed: Option<u64>,
@@ -27,11 +49,236 @@ struct Cli {
     /// Play in unlimited time mode (no 10-year limit)
     #[arg(short, long)]
     unlimited: bool,
+
+    /// Load a house ruleset from a TOML file instead of the default rules
+    #[arg(long, value_name = "FILE", global = true)]
+    rules: Option<PathBuf>,
+
+    /// Built-in ruleset: modern, or classic for the 1973 BASIC original
+    #[arg(long, value_name = "NAME", global = true, conflicts_with = "rules")]
+    ruleset: Option<String>,
+
+    /// Trade in silver: land is bought with the treasury and grain has a
+    /// market price
+    #[arg(long, global = true)]
+    economy: bool,
+
+    /// Levy a tax at the start of every year, at the cost of newcomers and
+    /// unrest
+    #[arg(long, global = true)]
+    taxes: bool,
+
+    /// Store grain in granaries: what they cannot hold rots, and building
+    /// more costs grain and labor
+    #[arg(long, global = true)]
+    granaries: bool,
+
+    /// Build canals, walls, temples and granaries over several years, paid
+    /// for in grain, labor and upkeep
+    #[arg(long, global = true)]
+    construction: bool,
+
+    /// Resume a reign from a save file (Ctrl+S saves during play)
+    #[arg(short, long, value_name = "FILE")]
+    load: Option<PathBuf>,
+
+    /// Record the reign to a replay file
+    #[arg(short, long, value_name = "FILE")]
+    record: Option<PathBuf>,
+
+    /// Built-in strategy consulted by the advisor (F2) and autoplay (F3)
+    #[arg(long, value_name = "NAME", default_value = "optimal")]
+    advisor: String,
+
+    /// Let the advisor rule from the start
+    #[arg(long)]
+    autoplay: bool,
+
+    /// Plan each year on a single screen (F4 toggles during play)
+    #[arg(long)]
+    planner: bool,
+
+    /// Warn before feeding once more than PERCENT of the people would starve
+    #[arg(long, value_name = "PERCENT", default_value_t = 0)]
+    warn_starvation: u32,
+
+    /// Feed without asking for confirmation after a warning
+    #[arg(long)]
+    no_confirm: bool,
+
+    /// Play as plain lines on stdin and stdout, for screen readers and slow
+    /// links
+    #[arg(long, conflicts_with_all = ["autoplay", "planner", "theme"])]
+    plain: bool,
+
+    /// Let another program play over stdin and stdout, one JSON message per
+    /// line
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        conflicts_with_all = ["load", "autoplay", "planner", "plain", "theme"]
+    )]
+    protocol: Option<Protocol>,
+
+    /// Colour theme: default, light, high-contrast, amber, green or none
+    #[arg(long, value_name = "NAME")]
+    theme: Option<String>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Protocol {
+    Json,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Play back a recorded reign (Enter/Space steps, A toggles auto-play)
+    Replay {
+        /// Replay file written with --record
+        file: PathBuf,
+
+        /// Re-simulate the reign without a terminal and check its final score
+        #[arg(long)]
+        verify: bool,
+    },
+
+    /// Play many reigns without a terminal and print aggregate statistics
+    Simulate {
+        /// Number of reigns to play
+        #[arg(short = 'n', long, default_value_t = 1000)]
+        games: u32,
+
+        /// Seed of the first reign, each following reign uses the next seed
+        #[arg(short, long, default_value_t = 0)]
+        seed: u64,
+
+        /// Built-in strategy to rule with instead of the --feed/--plant policy
+        #[arg(long, value_name = "NAME")]
+        strategy: Option<String>,
+
+        /// Bushels fed to each person every year, at most 1000
+        #[arg(long, default_value_t = 20, value_parser = value_parser!(u32).range(..=1000))]
+        feed: u32,
+
+        /// Percent of the plantable acres to sow every year
+        #[arg(long, default_value_t = 100, value_parser = value_parser!(u32).range(..=100))]
+        plant: u32,
+    },
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    let mut rules = match (&cli.rules, &cli.ruleset) {
+        (Some(path), _) => Rules::from_toml_file(path)?,
+        (None, Some(name)) => Rules::named(name).ok_or_else(|| {
+            anyhow!(
+                "unknown ruleset '{}', expected one of: modern, classic",
+                name
+            )
+        })?,
+        (None, None) => Rules::default(),
+    };
+    if cli.economy || cli.taxes || cli.granaries || cli.construction {
+        rules.economy.enabled |= cli.economy;
+        rules.taxation.enabled |= cli.taxes;
+        rules.granaries.enabled |= cli.granaries || cli.construction;
+        rules.construction.enabled |= cli.construction;
+        rules.validate()?;
+    }
+
+    if let Some(protocol) = cli.protocol {
+        if cli.command.is_some() {
+            return Err(anyhow!(
+                "--protocol plays a new reign and takes no subcommand"
+            ));
+        }
+
+        let seed = cli.seed.unwrap_or_else(rand::random);
+        let mut game = Game::with_rules(Some(seed), cli.unlimited, rules.clone());
+        let mut recorder = Recorder::new(seed, cli.unlimited, rules);
+        match protocol {
+            Protocol::Json => protocol::run_json(
+                &mut game,
+                &mut recorder,
+                &mut io::stdin().lock(),
+                &mut io::stdout(),
+            )?,
+        }
+        if let Some(path) = cli.record {
+            recorder.replay.write(&path)?;
+        }
+        return Ok(());
+    }
+
+    // Load files before touching the terminal so errors are readable
+    let mut app = match (cli.command, cli.load) {
+        (Some(Command::Replay { file, verify }), _) => {
+            let replay = Replay::read(&file)?;
+            if verify {
+                let score = replay.verify()?;
+                println!(
+                    "Replay verified: {:?}, {:.1}% deaths, {:.1} acres per person",
+                    score.rating, score.death_rate, score.acres_per_person
+                );
+                return Ok(());
+            }
+            App::replay(&replay)
+        }
+        (
+            Some(Command::Simulate {
+                games,
+                seed,
+                strategy,
+                feed,
+                plant,
+            }),
+            _,
+        ) => {
+            let mut strategy = match strategy {
+                Some(name) => find_strategy(&name)?,
+                None => Box::new(Policy {
+                    feed_per_person: feed,
+                    plant_percent: plant,
+                }),
+            };
+            let stats = simulate(games, seed, &rules, strategy.as_mut())?;
+            println!("Strategy:              {}", strategy.name());
+            print_stats(&stats);
+            return Ok(());
+        }
+        (None, Some(path)) => App::from_save(SaveFile::read(&path)?, path),
+        (None, None) => App::new(cli.seed, cli.unlimited, rules),
+    };
+    app.advisor = find_strategy(&cli.advisor)?;
+    app.autoplay = cli.autoplay;
+    app.feeding_warnings = FeedingWarnings {
+        starvation_percent: cli.warn_starvation,
+        confirm: !cli.no_confirm,
+    };
+    if cli.planner && app.playback.is_none() {
+        if !app.planner_offered() {
+            return Err(anyhow!(
+                "--planner cannot plan the grain trade or construction, play without it"
+            ));
+        }
+        app.planner = Some(Default::default());
+    }
+    app.theme = choose_theme(cli.theme)?;
+
+    if cli.plain {
+        if app.playback.is_some() {
+            return Err(anyhow!(
+                "replays are watched in the terminal, use --verify to check one without it"
+            ));
+        }
+        plain::run(&mut app, &mut io::stdin().lock(), &mut io::stdout())?;
+        if let Some(path) = cli.record {
+            app.recorder.replay.write(&path)?;
+        }
+        return Ok(());
+    }
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -39,9 +286,8 @@ fn main() -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create app and run
-    let app = App::new(cli.seed, cli.unlimited);
-    let res = run_app(&mut terminal, app);
+    // Run app
+    let res = run_app(&mut terminal, &mut app);
 
     // Restore terminal
     disable_raw_mode()?;
@@ -56,21 +302,101 @@ fn main() -> Result<()> {
         eprintln!("Error: {:?}", err);
     }
 
+    if let Some(path) = cli.record {
+        app.recorder.replay.write(&path)?;
+    }
+
     Ok(())
 }
 
-fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, mut app: App) -> Result<()> {
+fn find_strategy(name: &str) -> Result<Box<dyn Strategy>> {
+    builtin(name).ok_or_else(|| {
+        anyhow!(
+            "unknown strategy '{}', expected one of: {}",
+            name,
+            BUILTIN_STRATEGIES.join(", ")
+        )
+    })
+}
+
+fn print_stats(stats: &SimulationStats) {
+    let percent = |count: u32| {
+        if stats.games == 0 {
+            0.0
+        } else {
+            count as f64 * 100.0 / stats.games as f64
+        }
+    };
+
+    println!("Reigns simulated:      {}", stats.games);
+    println!(
+        "Survival rate:         {:.1}%",
+        stats.survival_rate() * 100.0
+    );
+    println!("Mean deaths:           {:.1}", stats.mean_deaths());
+    println!(
+        "Mean acres per person: {:.1}",
+        stats.mean_acres_per_person()
+    );
+    println!();
+    println!("Ratings:");
+    for (name, count) in [
+        ("Excellent", stats.excellent),
+        ("Good", stats.good),
+        ("Fair", stats.fair),
+        ("Poor", stats.poor),
+        ("Terrible", stats.terrible),
+    ] {
+        println!("  {:<10} {:>6} ({:>5.1}%)", name, count, percent(count));
+    }
+}
+
+/// The theme named on the command line, then in the config file, then no
+/// colour at all when `NO_COLOR` is set.
+fn choose_theme(name: Option<String>) -> Result<Theme> {
+    let config = Config::read(&default_config_path())?;
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    theme_for(name, config, no_color)
+}
+
+/// `choose_theme` with the config file and environment already read.
+fn theme_for(name: Option<String>, config: Config, no_color: bool) -> Result<Theme> {
+    match name.or(config.theme) {
+        Some(name) => Theme::named(&name).ok_or_else(|| {
+            anyhow!(
+                "unknown theme '{}', expected one of: {}",
+                name,
+                THEME_NAMES.join(", ")
+            )
+        }),
+        None if no_color => Ok(Theme::none()),
+        None => Ok(Theme::default()),
+    }
+}
+
+fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
     let events = EventHandler::new();
 
     loop {
-        terminal.draw(|f| ui::draw(f, &app))?;
+        terminal.draw(|f| ui::draw(f, app))?;
 
         match events.next()? {
+            Event::Key(key) if app.show_help => match key.code {
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    app.should_quit = true;
+                }
+                KeyCode::Esc | KeyCode::Enter | KeyCode::F(1) | KeyCode::Char('?') => {
+                    app.toggle_help();
+                }
+                KeyCode::Up => app.scroll_help(-1),
+                KeyCode::Down => app.scroll_help(1),
+                KeyCode::PageUp => app.scroll_help(-10),
+                KeyCode::PageDown => app.scroll_help(10),
+                _ => {}
+            },
             Event::Key(key) => match key.code {
-                KeyCode::Esc => {
-                    if key.modifiers.contains(KeyModifiers::NONE) {
-                        app.should_quit = true;
-                    }
+                KeyCode::Esc if key.modifiers.contains(KeyModifiers::NONE) => {
+                    app.should_quit = true;
                 }
                 KeyCode::Char('c') => {
                     if key.modifiers.contains(KeyModifiers::CONTROL) {
@@ -79,19 +405,83 @@ fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, mut app: Ap
                         app.handle_input('c');
                     }
                 }
+                KeyCode::Char('s') => {
+                    if key.modifiers.contains(KeyModifiers::CONTROL) {
+                        app.save_game();
+                    } else {
+                        app.handle_input('s');
+                    }
+                }
                 KeyCode::Enter => {
                     app.handle_enter()?;
                 }
                 KeyCode::Backspace => {
                     app.handle_backspace();
                 }
+                KeyCode::F(1) | KeyCode::Char('?') => {
+                    app.toggle_help();
+                }
+                KeyCode::F(2) => {
+                    app.show_advice();
+                }
+                KeyCode::F(3) => {
+                    app.toggle_autoplay();
+                }
+                KeyCode::F(4) => {
+                    app.toggle_planner();
+                }
+                KeyCode::Delete => {
+                    app.handle_delete();
+                }
+                KeyCode::Home => {
+                    app.handle_home();
+                }
+                KeyCode::End => {
+                    app.handle_end();
+                }
+                KeyCode::Left => {
+                    app.handle_left_right(-1);
+                }
+                KeyCode::Right => {
+                    app.handle_left_right(1);
+                }
+                KeyCode::Tab => {
+                    app.toggle_charts();
+                }
+                KeyCode::Up => {
+                    app.handle_up_down(-1);
+                }
+                KeyCode::Down => {
+                    app.handle_up_down(1);
+                }
+                KeyCode::PageUp => {
+                    app.scroll(-10);
+                }
+                KeyCode::PageDown => {
+                    app.scroll(10);
+                }
                 KeyCode::Char(c) => {
                     app.handle_input(c);
                 }
                 _ => {}
             },
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::Down(MouseButton::Left) => {
+                    let size = terminal.size()?;
+                    let area = ratatui::layout::Rect::new(0, 0, size.width, size.height);
+                    let preset = ui::preset_at(app, area, mouse.column, mouse.row);
+                    app.handle_click(preset)?;
+                }
+                MouseEventKind::ScrollUp => app.scroll_wheel(-1),
+                MouseEventKind::ScrollDown => app.scroll_wheel(1),
+                _ => {}
+            },
+            Event::Resize(width, height) => {
+                // The next draw lays the screen out for the new size
+                terminal.resize(ratatui::layout::Rect::new(0, 0, width, height))?;
+            }
             Event::Tick => {
-                app.check_splash_timeout();
+                app.tick();
             }
         }
 
@@ -102,3 +492,61 @@ fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, mut app: Ap
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn simulate_with(flag: &str, value: &str) -> clap::error::Result<Cli> {
+        Cli::try_parse_from(["hammurabi", "simulate", flag, value])
+    }
+
+    #[test]
+    fn absurd_policies_are_refused() {
+        assert!(simulate_with("--feed", "1000").is_ok());
+        assert!(simulate_with("--feed", "100000000").is_err());
+        assert!(simulate_with("--plant", "100").is_ok());
+        assert!(simulate_with("--plant", "101").is_err());
+    }
+
+    fn config(theme: Option<&str>) -> Config {
+        Config {
+            theme: theme.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn no_color_turns_off_the_default_colours() {
+        assert_eq!(
+            theme_for(None, config(None), false).unwrap(),
+            Theme::default()
+        );
+        assert_eq!(theme_for(None, config(None), true).unwrap(), Theme::none());
+    }
+
+    #[test]
+    fn a_named_theme_outranks_no_color() {
+        let light = Theme::light();
+        assert_eq!(
+            theme_for(Some("light".to_string()), config(None), true).unwrap(),
+            light
+        );
+        assert_eq!(theme_for(None, config(Some("light")), true).unwrap(), light);
+    }
+
+    #[test]
+    fn the_flag_outranks_the_config() {
+        let theme = theme_for(Some("light".to_string()), config(Some("amber")), false);
+        assert_eq!(theme.unwrap(), Theme::light());
+    }
+
+    #[test]
+    fn unknown_themes_are_refused() {
+        for (flag, file) in [(Some("sepia"), None), (None, Some("sepia"))] {
+            let err = theme_for(flag.map(str::to_string), config(file), false)
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains("unknown theme 'sepia'"), "{}", err);
+        }
+    }
+}
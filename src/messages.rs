@@ -1,20 +1,42 @@
-use rand::rngs::StdRng;
+use hammurabi::game::{Building, GameRng, YearEvent};
 use rand::seq::SliceRandom;
 use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct MessageTemplates {
-    rng: StdRng,
+    rng: GameRng,
 }
 
 impl MessageTemplates {
     pub fn new(seed: Option<u64>) -> Self {
         let rng = match seed {
-            Some(s) => StdRng::seed_from_u64(s),
-            None => StdRng::from_entropy(),
+            Some(s) => GameRng::seed_from_u64(s),
+            None => GameRng::from_entropy(),
         };
         Self { rng }
     }
 
+    pub fn event_message(&mut self, event: &YearEvent) -> String {
+        match *event {
+            YearEvent::Harvest {
+                yield_per_acre,
+                total,
+            } => self.harvest_message(yield_per_acre, total),
+            YearEvent::Rats { eaten, .. } => self.rats_message(eaten),
+            YearEvent::Spoilage { spoiled } => self.spoilage_message(spoiled),
+            YearEvent::Completed { building, count } => self.completed_message(building, count),
+            YearEvent::Raid { stolen, .. } => self.raid_message(stolen),
+            YearEvent::Upkeep { paid, ruined: 0 } => self.upkeep_message(paid),
+            YearEvent::Upkeep { paid, ruined } => self.ruin_message(paid, ruined),
+            YearEvent::Immigration { count: 0 } => self.no_immigration_message(),
+            YearEvent::Immigration { count } => self.immigration_message(count),
+            YearEvent::Plague { .. } => self.plague_message(),
+            YearEvent::Starvation { deaths } => self.starvation_message(deaths),
+            YearEvent::Unrest { level, change } => self.unrest_message(level, change),
+        }
+    }
+
     pub fn harvest_message(&mut self, yield_per_acre: u32, total: u32) -> String {
         let templates = [
             "THY HARVEST YIELDED {} BUSHELS PER ACRE, TOTAL: {} BUSHELS",
@@ -53,6 +75,67 @@ impl MessageTemplates {
         template.replace("{}", &amount.to_string())
     }
 
+    pub fn completed_message(&mut self, building: Building, count: u32) -> String {
+        let templates = [
+            "THY MASONS HAVE FINISHED {}!",
+            "BEHOLD! {} NOW STAND IN THY REALM!",
+            "THE LAST BRICK IS LAID - {} COMPLETED!",
+            "THY BUILDERS RETURN TO THE FIELDS, {} FINISHED!",
+        ];
+        let works = format!("{} {}", count, building.name(count));
+
+        let template = templates.choose(&mut self.rng).unwrap();
+        template.replace("{}", &works)
+    }
+
+    pub fn raid_message(&mut self, amount: u32) -> String {
+        let templates = [
+            "RAIDERS FROM THE HILLS CARRIED OFF {} BUSHELS!",
+            "BANDITS FELL UPON THY STORES AND STOLE {} BUSHELS!",
+            "THE NOMADS SWEPT IN BY NIGHT - {} BUSHELS ARE GONE!",
+            "A WAR BAND PLUNDERED {} BUSHELS OF THY GRAIN!",
+        ];
+
+        let template = templates.choose(&mut self.rng).unwrap();
+        template.replace("{}", &amount.to_string())
+    }
+
+    pub fn upkeep_message(&mut self, amount: u32) -> String {
+        let templates = [
+            "THY BUILDINGS WERE KEPT IN GOOD REPAIR FOR {} BUSHELS",
+            "{} BUSHELS WENT TO THE UPKEEP OF THY WORKS",
+            "THY CARETAKERS WERE PAID {} BUSHELS",
+        ];
+
+        let template = templates.choose(&mut self.rng).unwrap();
+        template.replace("{}", &amount.to_string())
+    }
+
+    pub fn ruin_message(&mut self, paid: u32, ruined: u32) -> String {
+        let templates = [
+            "THY STORES COULD NOT KEEP THY WORKS - {} FELL INTO RUIN, {} BUSHELS KEPT THE REST",
+            "UNPAID, THY CARETAKERS FLED - {} CRUMBLED, {} BUSHELS SPENT ON THE REST",
+        ];
+
+        let template = templates.choose(&mut self.rng).unwrap();
+        template
+            .replacen("{}", &ruined.to_string(), 1)
+            .replacen("{}", &paid.to_string(), 1)
+    }
+
+    pub fn spoilage_message(&mut self, amount: u32) -> String {
+        let templates = [
+            "THY GRANARIES OVERFLOWED - {} BUSHELS ROTTED IN THE OPEN!",
+            "FOR WANT OF STOREHOUSES, {} BUSHELS SPOILED!",
+            "MOULD AND DAMP CLAIMED {} BUSHELS HEAPED BEYOND THY GRANARIES!",
+            "{} BUSHELS LAY UNSHELTERED AND ROTTED AWAY!",
+            "THE RAINS RUINED {} BUSHELS THAT THY GRANARIES COULD NOT HOLD!",
+        ];
+
+        let template = templates.choose(&mut self.rng).unwrap();
+        template.replace("{}", &amount.to_string())
+    }
+
     pub fn plague_message(&mut self) -> String {
         let templates = [
             "A GREAT PESTILENCE HATH SWEPT THY KINGDOM! HALF THY SUBJECTS PERISHED!",
@@ -191,6 +274,28 @@ impl MessageTemplates {
         templates.choose(&mut self.rng).unwrap().to_string()
     }
 
+    pub fn insufficient_grain_works_message(&mut self) -> String {
+        let templates = [
+            "THY STORES CANNOT PAY FOR SO MANY WORKS!",
+            "THE MASONS DEMAND MORE GRAIN THAN THOU HAST!",
+            "NOT ENOUGH GRAIN TO RAISE SUCH BUILDINGS!",
+            "THOU WOULDST EMPTY THY STORES TO BUILD THEM!",
+        ];
+
+        templates.choose(&mut self.rng).unwrap().to_string()
+    }
+
+    pub fn insufficient_builders_message(&mut self) -> String {
+        let templates = [
+            "TOO FEW SUBJECTS REMAIN TO RAISE SUCH WORKS!",
+            "THY PEOPLE CANNOT BUILD SO MUCH AT ONCE!",
+            "NOT ENOUGH HANDS TO LAY SO MANY BRICKS!",
+            "THY WORKFORCE CANNOT RAISE SO MANY BUILDINGS!",
+        ];
+
+        templates.choose(&mut self.rng).unwrap().to_string()
+    }
+
     pub fn insufficient_workers_message(&mut self) -> String {
         let templates = [
             "TOO FEW SUBJECTS REMAIN TO TILL SUCH FIELDS!",
@@ -224,4 +329,52 @@ impl MessageTemplates {
 
         templates.choose(&mut self.rng).unwrap().to_string()
     }
+
+    pub fn insufficient_silver_message(&mut self) -> String {
+        let templates = [
+            "THY TREASURY HOLDS NOT SO MUCH SILVER!",
+            "THE SHEKELS IN THY COFFERS FALL SHORT!",
+            "THY TREASURER SHAKES HIS HEAD - NOT ENOUGH SILVER!",
+            "SUCH A PRICE WOULD EMPTY THY TREASURY AND MORE!",
+            "THE MERCHANTS DEMAND SILVER THOU HAST NOT!",
+        ];
+
+        templates.choose(&mut self.rng).unwrap().to_string()
+    }
+
+    pub fn insufficient_grain_sale_message(&mut self) -> String {
+        let templates = [
+            "THOU CANST NOT SELL GRAIN THOU HAST NOT!",
+            "THY GRANARIES HOLD NOT SO MUCH TO SELL!",
+            "THE MERCHANTS WAIT IN VAIN FOR SUCH A STORE!",
+            "CHECK THY STORES - THERE IS LESS GRAIN TO SELL!",
+            "THY GRAIN IS NOT SO PLENTIFUL!",
+        ];
+
+        templates.choose(&mut self.rng).unwrap().to_string()
+    }
+
+    pub fn unrest_message(&mut self, level: u32, change: i32) -> String {
+        let templates: &[&str] = match change.cmp(&0) {
+            std::cmp::Ordering::Greater => &[
+                "THY TAX COLLECTORS ARE CURSED IN THE STREETS - UNREST RISES TO {}",
+                "DISCONTENT SPREADS THROUGH THE MARKETS - UNREST STANDS AT {}",
+                "THY PEOPLE GRUMBLE AT THEIR BURDEN - UNREST GROWS TO {}",
+                "SEDITIOUS WHISPERS FILL THE TAVERNS - UNREST REACHES {}",
+            ],
+            _ if level == 0 => &[
+                "THY PEOPLE ARE CONTENT WITH THEIR LOT",
+                "NO VOICE IS RAISED AGAINST THY TAXES",
+                "PEACE REIGNS IN THE STREETS OF THY CITY",
+            ],
+            _ => &[
+                "THY PEOPLE GROW CALMER - UNREST FALLS TO {}",
+                "THE GRUMBLING SUBSIDES - UNREST EASES TO {}",
+                "TEMPERS COOL IN THE MARKETS - UNREST STANDS AT {}",
+            ],
+        };
+
+        let template = templates.choose(&mut self.rng).unwrap();
+        template.replace("{}", &level.to_string())
+    }
 }
@@ -0,0 +1,448 @@
+//! The game as a teletype would have played it: the report and prompts as
+//! plain lines on stdout and one answer per line on stdin, for screen
+//! readers and slow links. Every answer goes through the same [`App`] as the
+//! terminal UI.
+
+use crate::app::App;
+use crate::help::help_sections;
+use anyhow::Result;
+use hammurabi::game::{classic, describe_price_change, GamePhase};
+use std::io::{BufRead, Write};
+
+pub fn run(app: &mut App, input: &mut impl BufRead, output: &mut impl Write) -> Result<()> {
+    app.planner = None;
+    let is_classic = app.game.rules.is_classic();
+
+    if app.game.current_phase == GamePhase::Splash {
+        if is_classic {
+            print_lines(output, &classic::opening())?;
+            print_lines(output, &classic::year_report(&app.game))?;
+        } else {
+            print_lines(output, &help_sections(app)[0].lines)?;
+            writeln!(
+                output,
+                "TYPE ? FOR COUNSEL, ADVICE FOR THY ADVISOR, UNDO TO TAKE BACK A DECREE."
+            )?;
+        }
+        app.handle_enter()?;
+    }
+
+    let mut reported_year = None;
+    // The last answer given at a prompt that asked again, repeated by an
+    // empty line so a warned ration can be confirmed
+    let mut retry = None;
+
+    loop {
+        match app.game.current_phase.clone() {
+            GamePhase::Splash | GamePhase::Instructions => app.handle_enter()?,
+            GamePhase::YearEnd if is_classic => {
+                print_lines(output, &classic::year_report(&app.game))?;
+                app.handle_enter()?;
+            }
+            GamePhase::YearEnd => {
+                print_lines(output, &event_lines(app))?;
+                app.handle_enter()?;
+            }
+            GamePhase::GameOver => {
+                if !is_classic {
+                    print_lines(output, &event_lines(app))?;
+                    writeln!(output)?;
+                }
+                print_lines(output, &app.final_report)?;
+                if is_classic {
+                    print_lines(output, &classic::farewell())?;
+                }
+                return Ok(());
+            }
+            phase => {
+                if !is_classic && reported_year != Some(app.game.year) {
+                    report(app, output)?;
+                    reported_year = Some(app.game.year);
+                }
+
+                let Some(mut answer) = ask(app, input, output)? else {
+                    return Ok(());
+                };
+                if answer.is_empty() && phase == GamePhase::Feeding {
+                    answer = retry.take().unwrap_or_default();
+                }
+
+                match answer.to_lowercase().as_str() {
+                    "?" => {
+                        for section in help_sections(app) {
+                            writeln!(output, "{}", section.title)?;
+                            print_lines(output, &section.lines)?;
+                            writeln!(output)?;
+                        }
+                        continue;
+                    }
+                    "advice" => {
+                        app.show_advice();
+                        writeln!(output, "{}", app.message)?;
+                        app.message.clear();
+                        continue;
+                    }
+                    "undo" => {
+                        app.step_back();
+                        app.input.clear();
+                        continue;
+                    }
+                    "save" => {
+                        app.save_game();
+                        writeln!(output, "{}", app.message)?;
+                        app.message.clear();
+                        continue;
+                    }
+                    _ => {}
+                }
+
+                app.input.set(answer.clone());
+                app.handle_enter()?;
+                if app.game.current_phase == phase {
+                    retry = Some(answer);
+                }
+                if !app.message.is_empty() {
+                    writeln!(output, "{}", app.message)?;
+                    app.message.clear();
+                }
+            }
+        }
+    }
+}
+
+fn event_lines(app: &App) -> Vec<String> {
+    app.event_messages
+        .iter()
+        .map(|message| message.text.clone())
+        .collect()
+}
+
+/// The state of the kingdom at the start of a year.
+fn report(app: &App, output: &mut impl Write) -> Result<()> {
+    let game = &app.game;
+    writeln!(output)?;
+    writeln!(
+        output,
+        "HAMMURABI: I BEG TO REPORT TO THEE, IN YEAR {} OF THY REIGN",
+        game.year
+    )?;
+    write!(output, "BUSHELS: {}  ACRES: {}  ", game.grain, game.land)?;
+    if game.rules.uses_silver() {
+        write!(output, "SILVER: {}  ", game.silver)?;
+    }
+    writeln!(output, "PEOPLE: {}", game.population)?;
+    Ok(())
+}
+
+/// Asks the question of the current phase and reads the answer, or `None`
+/// at the end of the input.
+fn ask(app: &mut App, input: &mut impl BufRead, output: &mut impl Write) -> Result<Option<String>> {
+    if app.selling_land() {
+        return read_answer(input, output, classic::SELL_PROMPT);
+    }
+    if app.game.rules.is_classic() && app.game.current_phase == GamePhase::LandTransaction {
+        writeln!(output, "{}", classic::land_price(app.game.land_price))?;
+        return read_answer(input, output, classic::BUY_PROMPT);
+    }
+
+    let game = &app.game;
+    if game.rules.is_classic() {
+        return match game.current_phase {
+            GamePhase::Feeding => {
+                writeln!(output)?;
+                read_answer(input, output, classic::FEED_PROMPT)
+            }
+            _ => {
+                writeln!(output)?;
+                read_answer(input, output, classic::PLANT_PROMPT)
+            }
+        };
+    }
+
+    let (question, hint) = match game.current_phase {
+        GamePhase::Taxation => {
+            let taxation = &game.rules.taxation;
+            (
+                "WHAT TAX WILT THOU LEVY, IN PERCENT",
+                format!(
+                    "THY LIMIT: {}, LAST YEAR: {}, UNREST: {} OF {}",
+                    taxation.max_rate, game.tax_rate, game.unrest, taxation.revolt_unrest
+                ),
+            )
+        }
+        GamePhase::LandTransaction if app.trading_grain() => {
+            writeln!(
+                output,
+                "GRAIN IS TRADING AT {} SHEKELS PER HUNDRED BUSHELS.",
+                game.grain_price
+            )?;
+            (
+                "HOW MANY BUSHELS WILT THOU BUY WITH SILVER",
+                format!(
+                    "NEGATIVE TO SELL, THOU CANST BUY: {}, THY STORES: {}",
+                    game.grain_for_silver(game.silver),
+                    game.grain
+                ),
+            )
+        }
+        GamePhase::LandTransaction => {
+            write!(
+                output,
+                "LAND IS TRADING AT {} {} PER ACRE.",
+                game.land_price,
+                game.rules.land_currency()
+            )?;
+            match game.price_change() {
+                Some(change) => writeln!(output, " ({})", describe_price_change(change))?,
+                None => writeln!(output)?,
+            }
+            (
+                "HOW MANY ACRES DOST THOU WISH TO ACQUIRE",
+                format!(
+                    "NEGATIVE TO SELL, THOU CANST BUY: {}, THY HOLDINGS: {}",
+                    game.affordable_acres(),
+                    game.land
+                ),
+            )
+        }
+        GamePhase::Construction => {
+            let costs: Vec<String> = game
+                .rules
+                .buildable()
+                .into_iter()
+                .map(|building| {
+                    let project = game.rules.project(building);
+                    format!(
+                        "{} {} BUSHELS, {} WORKERS, {} {}",
+                        building.name(1),
+                        project.grain_cost,
+                        project.workers,
+                        project.years,
+                        if project.years == 1 { "YEAR" } else { "YEARS" }
+                    )
+                })
+                .collect();
+            (
+                "WHAT SHALL THY PEOPLE BUILD",
+                format!(
+                    "{}; UNDER WAY: {}",
+                    costs.join("; "),
+                    game.works_under_way()
+                ),
+            )
+        }
+        GamePhase::Planting => (
+            "HOW MANY ACRES WILT THOU PLANT WITH SEED",
+            format!("THY LIMIT: {}", game.max_plantable_acres()),
+        ),
+        _ => (
+            "HOW MANY BUSHELS SHALL FEED THY PEOPLE",
+            format!(
+                "THY PEOPLE REQUIRE: {}, THY STORES HOLD: {}",
+                game.grain_needed_for_feeding(),
+                game.grain
+            ),
+        ),
+    };
+    writeln!(output, "({})", hint)?;
+    read_answer(input, output, question)
+}
+
+fn read_answer(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    prompt: &str,
+) -> Result<Option<String>> {
+    write!(output, "{}? ", prompt)?;
+    output.flush()?;
+
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        writeln!(output)?;
+        return Ok(None);
+    }
+    Ok(Some(line.trim().to_string()))
+}
+
+fn print_lines(output: &mut impl Write, lines: &[String]) -> Result<()> {
+    for line in lines {
+        writeln!(output, "{}", line)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hammurabi::game::Rules;
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use std::io;
+    use std::rc::Rc;
+
+    /// The roll of paper a teletype prints on, shared by the printer and
+    /// the keyboard that echoes what is typed.
+    #[derive(Clone, Default)]
+    struct Paper(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Paper {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Types one answer at a time, echoing each onto the paper as the
+    /// program reads it.
+    struct Keyboard {
+        answers: VecDeque<String>,
+        line: Vec<u8>,
+        paper: Paper,
+    }
+
+    impl io::Read for Keyboard {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let available = self.fill_buf()?;
+            let len = available.len().min(buf.len());
+            buf[..len].copy_from_slice(&available[..len]);
+            self.consume(len);
+            Ok(len)
+        }
+    }
+
+    impl BufRead for Keyboard {
+        fn fill_buf(&mut self) -> io::Result<&[u8]> {
+            if self.line.is_empty() {
+                if let Some(answer) = self.answers.pop_front() {
+                    self.line = format!("{}\n", answer).into_bytes();
+                    self.paper.write_all(&self.line)?;
+                }
+            }
+            Ok(&self.line)
+        }
+
+        fn consume(&mut self, amount: usize) {
+            self.line.drain(..amount);
+        }
+    }
+
+    /// Everything printed and typed while `answers` are given to a reign.
+    fn transcript(rules: Rules, seed: u64, answers: &[&str]) -> String {
+        let paper = Paper::default();
+        let mut keyboard = Keyboard {
+            answers: answers.iter().map(|answer| answer.to_string()).collect(),
+            line: Vec::new(),
+            paper: paper.clone(),
+        };
+        let mut app = App::new(Some(seed), false, rules);
+        run(&mut app, &mut keyboard, &mut paper.clone()).unwrap();
+        let bytes = paper.0.borrow().clone();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    include!("../tests/golden/reigns.rs");
+
+    /// The answers of a classic reign, one to a prompt.
+    fn classic_answers(years: &[[u32; 4]]) -> Vec<String> {
+        let mut answers = Vec::new();
+        for &[buy, sell, feed, plant] in years {
+            answers.push(buy.to_string());
+            if buy == 0 {
+                answers.push(sell.to_string());
+            }
+            answers.extend([feed.to_string(), plant.to_string()]);
+        }
+        answers
+    }
+
+    #[test]
+    fn golden_reigns_through_plain_mode() {
+        for reign in GOLDEN_REIGNS {
+            let answers = classic_answers(reign.years);
+            let answers: Vec<&str> = answers.iter().map(String::as_str).collect();
+            assert_golden(reign, &transcript(Rules::classic(), reign.seed, &answers));
+        }
+    }
+
+    #[test]
+    fn a_negative_answer_loses_the_classic_steward() {
+        let resigned = [
+            "",
+            "HAMURABI:  I CANNOT DO WHAT YOU WISH.",
+            "GET YOURSELF ANOTHER STEWARD!!!!!",
+            "",
+            "\u{7}\u{7}\u{7}\u{7}\u{7}\u{7}\u{7}\u{7}\u{7}\u{7}SO LONG FOR NOW.",
+            "",
+        ]
+        .map(|line| format!("{}\n", line))
+        .concat();
+        for answers in [
+            &["-5"][..],
+            &["0", "-5"],
+            &["0", "0", "-2000"],
+            &["0", "0", "2000", "-1"],
+        ] {
+            let transcript = transcript(Rules::classic(), 1, answers);
+            let last = answers.last().unwrap();
+            assert!(
+                transcript.ends_with(&format!("? {}\n{}", last, resigned)),
+                "{}",
+                transcript
+            );
+        }
+    }
+
+    #[test]
+    fn counsel_undo_and_a_confirmed_ration() {
+        let answers = ["0", "?", "undo", "10", "500", "1900", "", ""];
+        let transcript = transcript(Rules::default(), 1, &answers);
+        let asked = |question: &str| format!("{}? ", question);
+        let plant = asked("HOW MANY ACRES WILT THOU PLANT WITH SEED");
+        let acquire = asked("HOW MANY ACRES DOST THOU WISH TO ACQUIRE");
+        let feed = asked("HOW MANY BUSHELS SHALL FEED THY PEOPLE");
+
+        // Counsel is printed and the same question asked again
+        let counsel = transcript.split_once(&format!("{}?\n", plant)).unwrap().1;
+        let counsel = counsel.split_once(&plant).unwrap().0;
+        for section in ["THE MATTER BEFORE THEE", "THE LAWS OF THE LAND"] {
+            assert!(counsel.contains(section), "{}", transcript);
+        }
+
+        // Undo goes back to the land, and the trade answered again is kept
+        let undone = transcript
+            .split_once(&format!("{}undo\n", plant))
+            .unwrap()
+            .1;
+        assert!(
+            undone.starts_with("LAND IS TRADING") && undone.contains(&format!("{}10\n", acquire)),
+            "{}",
+            transcript
+        );
+        assert!(
+            transcript.contains("THY STORES HOLD: 2050)"),
+            "{}",
+            transcript
+        );
+
+        // The warned ration is handed out on an empty line
+        let warned = transcript.split_once(&format!("{}1900\n", feed)).unwrap().1;
+        assert!(warned.starts_with("BEWARE! 5 OF THY 100"), "{}", transcript);
+        assert!(
+            warned.contains(&format!(
+                "{}\nTHY GRANARIES FAILED 5 WHO NOW LIE DEAD",
+                feed
+            )),
+            "{}",
+            transcript
+        );
+        assert!(
+            transcript.contains("IN YEAR 2 OF THY REIGN"),
+            "{}",
+            transcript
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use hammurabi::game::YearDecisions;
+
+pub const FIELD_NAMES: [&str; 3] = ["TRADE", "SOW", "FEED"];
+
+/// The three decisions of a year typed side by side: acres to trade, acres
+/// to sow and bushels to feed. Empty fields count as zero.
+#[derive(Default)]
+pub struct Planner {
+    pub fields: [String; 3],
+    pub focus: usize,
+}
+
+impl Planner {
+    pub fn input(&mut self) -> &mut String {
+        &mut self.fields[self.focus]
+    }
+
+    pub fn move_focus(&mut self, delta: i32) {
+        self.focus = (self.focus as i32 + delta).clamp(0, 2) as usize;
+    }
+
+    /// The typed decisions, or `None` while a field does not hold a number.
+    pub fn decisions(&self) -> Option<YearDecisions> {
+        Some(YearDecisions {
+            land: parse_field(&self.fields[0])?,
+            plant: parse_field(&self.fields[1])?,
+            feed: parse_field(&self.fields[2])?,
+            ..YearDecisions::default()
+        })
+    }
+
+    pub fn fill(&mut self, decisions: &YearDecisions) {
+        self.fields = [
+            decisions.land.to_string(),
+            decisions.plant.to_string(),
+            decisions.feed.to_string(),
+        ];
+    }
+}
+
+fn parse_field<T: std::str::FromStr + Default>(field: &str) -> Option<T> {
+    if field.trim().is_empty() {
+        Some(T::default())
+    } else {
+        field.trim().parse().ok()
+    }
+}
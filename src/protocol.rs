@@ -0,0 +1,237 @@
+//! Lets any process play a reign over stdin and stdout. Every message is one
+//! line of JSON with a `type` field. The game sends its `rules` once, then
+//! the `state` awaiting each year's decisions. The bot answers with a line
+//! such as `{"land": -10, "plant": 900, "feed": 2000}`, and the game replies
+//! with the `year`'s events or an `error`, until `game_over`.
+
+use crate::replay::Recorder;
+use anyhow::Result;
+use hammurabi::game::{
+    Game, GameAction, Rejection, Rules, Score, YearDecisions, YearError, YearEvent,
+};
+use serde::Serialize;
+use std::io::{BufRead, Write};
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Message<'a> {
+    Rules {
+        seed: u64,
+        unlimited: bool,
+        rules: &'a Rules,
+    },
+    State {
+        year: u32,
+        grain: u32,
+        land: u32,
+        population: u32,
+        land_price: u32,
+        /// Shekels in the treasury, under the silver economy only.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        silver: Option<u32>,
+        /// Shekels a hundred bushels trade for, under the silver economy only.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        grain_price: Option<u32>,
+        /// Last year's tax rate and the people's unrest, when taxes are
+        /// levied.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        tax_rate: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        unrest: Option<u32>,
+        /// Granaries standing and the bushels they hold, when the rules
+        /// have granaries.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        granaries: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        granary_capacity: Option<u32>,
+        /// Canals, walls and temples standing and the people at work on
+        /// buildings, when the rules allow building them.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        canals: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        walls: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        temples: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        builders: Option<u32>,
+        /// Most acres the grain in store, or the treasury, can buy.
+        max_buy: u32,
+        /// Most acres that can be sown before any land is traded.
+        max_plant: u32,
+        /// Bushels that would feed everyone.
+        grain_needed: u32,
+    },
+    Year {
+        year: u32,
+        events: &'a [YearEvent],
+    },
+    Error {
+        error: ErrorKind,
+        /// The decision that could not be carried out, if one was read.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        action: Option<&'a GameAction>,
+        message: String,
+    },
+    GameOver {
+        impeached: bool,
+        overthrown: bool,
+        score: Score,
+    },
+}
+
+/// Why a line was refused. The first seven mirror [`Rejection`].
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ErrorKind {
+    InsufficientGrain,
+    InsufficientLand,
+    InsufficientPopulation,
+    InsufficientSilver,
+    /// Grain was traded without the silver economy.
+    MarketClosed,
+    TaxTooHigh,
+    CannotBuild,
+    /// The line is not a set of decisions.
+    InvalidDecisions,
+    GameOver,
+}
+
+/// Plays a reign of `game` over JSON lines until it ends or `input` does.
+pub fn run_json(
+    game: &mut Game,
+    recorder: &mut Recorder,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<()> {
+    send(
+        output,
+        &Message::Rules {
+            seed: recorder.replay.seed,
+            unlimited: game.state().unlimited_mode,
+            rules: &game.state().rules,
+        },
+    )?;
+    send_state(game, output)?;
+
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let decisions: YearDecisions = match serde_json::from_str(&line) {
+            Ok(decisions) => decisions,
+            Err(err) => {
+                send(
+                    output,
+                    &Message::Error {
+                        error: ErrorKind::InvalidDecisions,
+                        action: None,
+                        message: err.to_string(),
+                    },
+                )?;
+                continue;
+            }
+        };
+
+        let actions = decisions.actions(&game.state().rules);
+        let report = match game.play_actions(&actions) {
+            Ok(report) => report,
+            Err(err) => {
+                send(output, &error_message(&err))?;
+                continue;
+            }
+        };
+
+        for action in actions {
+            recorder.record(action);
+        }
+        recorder.finish_year();
+        send(
+            output,
+            &Message::Year {
+                year: report.year,
+                events: &report.events,
+            },
+        )?;
+
+        if report.game_over {
+            let score = game.score();
+            recorder.finish_reign(score.clone());
+            return send(
+                output,
+                &Message::GameOver {
+                    impeached: game.state().was_impeached(),
+                    overthrown: game.state().was_overthrown(),
+                    score,
+                },
+            );
+        }
+        send_state(game, output)?;
+    }
+}
+
+fn error_message(err: &YearError) -> Message<'_> {
+    match err {
+        YearError::GameOver => Message::Error {
+            error: ErrorKind::GameOver,
+            action: None,
+            message: err.to_string(),
+        },
+        YearError::Rejected { action, reason } => Message::Error {
+            error: match reason {
+                Rejection::InsufficientGrain => ErrorKind::InsufficientGrain,
+                Rejection::InsufficientLand => ErrorKind::InsufficientLand,
+                Rejection::InsufficientPopulation => ErrorKind::InsufficientPopulation,
+                Rejection::InsufficientSilver => ErrorKind::InsufficientSilver,
+                Rejection::MarketClosed => ErrorKind::MarketClosed,
+                Rejection::TaxTooHigh => ErrorKind::TaxTooHigh,
+                Rejection::CannotBuild => ErrorKind::CannotBuild,
+            },
+            action: Some(action),
+            message: err.to_string(),
+        },
+    }
+}
+
+fn send_state(game: &Game, output: &mut impl Write) -> Result<()> {
+    let state = game.state();
+    let silver = state.rules.uses_silver();
+    let taxed = state.rules.taxation.enabled;
+    let stored = state.rules.granaries.enabled;
+    let building = state.rules.construction.enabled;
+    send(
+        output,
+        &Message::State {
+            year: state.year,
+            grain: state.grain,
+            land: state.land,
+            population: state.population,
+            land_price: state.land_price,
+            silver: silver.then_some(state.silver),
+            grain_price: silver.then_some(state.grain_price),
+            tax_rate: taxed.then_some(state.tax_rate),
+            unrest: taxed.then_some(state.unrest),
+            granaries: stored.then_some(state.granaries),
+            granary_capacity: stored.then_some(state.granary_capacity()),
+            canals: building.then_some(state.canals),
+            walls: building.then_some(state.walls),
+            temples: building.then_some(state.temples),
+            builders: state.rules.builds().then_some(state.builders()),
+            max_buy: state.affordable_acres(),
+            max_plant: state.max_plantable_acres(),
+            grain_needed: state.grain_needed_for_feeding(),
+        },
+    )
+}
+
+fn send(output: &mut impl Write, message: &Message) -> Result<()> {
+    serde_json::to_writer(&mut *output, message)?;
+    writeln!(output)?;
+    output.flush()?;
+    Ok(())
+}
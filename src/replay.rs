@@ -0,0 +1,141 @@
+use hammurabi::game::{GameAction, GamePhase, Replay, Rules, Score};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+pub const AUTO_PLAY_DELAY: Duration = Duration::from_millis(700);
+
+/// Collects the actions of the reign as they are taken.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Recorder {
+    pub replay: Replay,
+    current_year: Vec<GameAction>,
+}
+
+impl Recorder {
+    pub fn new(seed: u64, unlimited: bool, rules: Rules) -> Self {
+        Self {
+            replay: Replay::new(seed, unlimited, rules),
+            current_year: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, action: GameAction) {
+        self.current_year.push(action);
+    }
+
+    pub fn finish_year(&mut self) {
+        self.replay
+            .years
+            .push(std::mem::take(&mut self.current_year));
+    }
+
+    pub fn finish_reign(&mut self, score: Score) {
+        self.replay.score = Some(score);
+    }
+}
+
+/// Feeds recorded actions back into the app as if they were typed.
+pub struct Playback {
+    years: VecDeque<VecDeque<GameAction>>,
+    /// Whether land is bought and sold at the listing's separate prompts.
+    classic: bool,
+    pub auto: bool,
+    last_step: Instant,
+}
+
+impl Playback {
+    pub fn new(replay: &Replay) -> Self {
+        Self {
+            years: replay
+                .years
+                .iter()
+                .map(|actions| actions.iter().cloned().collect())
+                .collect(),
+            classic: replay.rules.is_classic(),
+            auto: false,
+            last_step: Instant::now(),
+        }
+    }
+
+    /// The input the ruler gave at this phase, or `None` once the
+    /// recording is exhausted. `trading_grain` is set when the land phase
+    /// asks for the silver economy's grain trade, and `selling_land` when
+    /// the classic steward asks what to sell.
+    pub fn next_input(
+        &mut self,
+        phase: &GamePhase,
+        trading_grain: bool,
+        selling_land: bool,
+    ) -> Option<String> {
+        self.last_step = Instant::now();
+
+        match phase {
+            GamePhase::Splash | GamePhase::Instructions => Some(String::new()),
+            GamePhase::YearEnd => {
+                self.years.pop_front();
+                Some(String::new())
+            }
+            GamePhase::Taxation => match self.years.front_mut()?.pop_front()? {
+                GameAction::SetTaxRate(rate) => Some(rate.to_string()),
+                _ => None,
+            },
+            GamePhase::LandTransaction if trading_grain => {
+                let year = self.years.front_mut()?;
+                match year.front() {
+                    Some(GameAction::BuyGrain(bushels)) => {
+                        let input = bushels.to_string();
+                        year.pop_front();
+                        Some(input)
+                    }
+                    Some(GameAction::SellGrain(bushels)) => {
+                        let input = format!("-{}", bushels);
+                        year.pop_front();
+                        Some(input)
+                    }
+                    _ => Some("0".to_string()),
+                }
+            }
+            GamePhase::LandTransaction => {
+                let year = self.years.front_mut()?;
+                match year.front() {
+                    Some(GameAction::BuyLand(acres)) => {
+                        let input = acres.to_string();
+                        year.pop_front();
+                        Some(input)
+                    }
+                    Some(GameAction::SellLand(_)) if self.classic && !selling_land => {
+                        Some("0".to_string())
+                    }
+                    Some(GameAction::SellLand(acres)) => {
+                        let input = if self.classic {
+                            acres.to_string()
+                        } else {
+                            format!("-{}", acres)
+                        };
+                        year.pop_front();
+                        Some(input)
+                    }
+                    _ => Some("0".to_string()),
+                }
+            }
+            GamePhase::Construction => match self.years.front_mut()?.pop_front()? {
+                GameAction::Build(works) => Some(works.to_string()),
+                _ => None,
+            },
+            GamePhase::Planting => match self.years.front_mut()?.pop_front()? {
+                GameAction::PlantAcres(acres) => Some(acres.to_string()),
+                _ => None,
+            },
+            GamePhase::Feeding => match self.years.front_mut()?.pop_front()? {
+                GameAction::FeedPopulation(bushels) => Some(bushels.to_string()),
+                _ => None,
+            },
+            GamePhase::GameOver => None,
+        }
+    }
+
+    pub fn is_due(&self) -> bool {
+        self.auto && self.last_step.elapsed() >= AUTO_PLAY_DELAY
+    }
+}
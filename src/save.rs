@@ -0,0 +1,150 @@
+use crate::app::{DraftStep, EventMessage};
+use crate::messages::MessageTemplates;
+use crate::replay::Recorder;
+use anyhow::{bail, Context, Result};
+use hammurabi::game::GameState;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Bumped whenever the layout of `SaveFile` changes incompatibly.
+pub const SAVE_VERSION: u32 = 12;
+
+#[derive(Serialize, Deserialize)]
+pub struct SaveFile {
+    pub version: u32,
+    pub game: GameState,
+    pub messages: MessageTemplates,
+    pub event_messages: Vec<EventMessage>,
+    pub draft: Vec<DraftStep>,
+    pub recorder: Recorder,
+}
+
+impl SaveFile {
+    pub fn read(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read save file {}", path.display()))?;
+        let save: SaveFile = serde_json::from_str(&contents)
+            .with_context(|| format!("failed to parse save file {}", path.display()))?;
+
+        if save.version != SAVE_VERSION {
+            bail!(
+                "save file {} has version {}, expected {}",
+                path.display(),
+                save.version,
+                SAVE_VERSION
+            );
+        }
+
+        Ok(save)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(path, contents)
+            .with_context(|| format!("failed to write save file {}", path.display()))
+    }
+}
+
+/// `$XDG_DATA_HOME/hammurabi/save.json`, or the platform equivalent.
+pub fn default_save_path() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("hammurabi")
+        .join("save.json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::App;
+    use hammurabi::game::{GamePhase, Rules};
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("hammurabi-{}-{}.json", name, std::process::id()))
+    }
+
+    fn answer(app: &mut App, input: &str) {
+        app.input.set(input.to_string());
+        app.handle_enter().unwrap();
+    }
+
+    /// Everything a save keeps, for comparing two apps.
+    fn snapshot(app: &App) -> serde_json::Value {
+        serde_json::json!({
+            "game": app.game,
+            "messages": app.messages,
+            "event_messages": app.event_messages,
+            "draft": app.draft,
+            "recorder": app.recorder,
+        })
+    }
+
+    /// A reign saved halfway through its second year, after the land was
+    /// traded.
+    fn saved_reign(path: &Path) -> App {
+        let mut app = App::new(Some(7), false, Rules::default());
+        app.save_path = path.to_path_buf();
+        answer(&mut app, "");
+        answer(&mut app, "");
+        for input in ["10", "500", "2000", ""] {
+            answer(&mut app, input);
+        }
+        answer(&mut app, "-20");
+        assert_eq!(app.game.year, 2);
+        assert_eq!(app.game.current_phase, GamePhase::Planting);
+        app.save_game();
+        app
+    }
+
+    #[test]
+    fn a_saved_reign_resumes_where_it_stopped() {
+        let path = temp_path("round-trip");
+        let mut original = saved_reign(&path);
+        let mut restored = App::from_save(SaveFile::read(&path).unwrap(), path.clone());
+        fs::remove_file(&path).unwrap();
+        assert_eq!(snapshot(&restored), snapshot(&original));
+
+        // The random streams carry on as if the reign had never stopped
+        for app in [&mut original, &mut restored] {
+            answer(app, "100");
+            let feed = app.game.grain_needed_for_feeding().min(app.game.grain);
+            answer(app, &feed.to_string());
+            if app.game.current_phase == GamePhase::Feeding {
+                // Confirm the starvation warning
+                answer(app, &feed.to_string());
+            }
+            answer(app, "");
+        }
+        assert_eq!(restored.game.year, 3);
+        assert_eq!(snapshot(&restored), snapshot(&original));
+    }
+
+    #[test]
+    fn saves_from_older_versions_are_refused() {
+        let path = temp_path("old-version");
+        saved_reign(&path);
+        let mut save: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+
+        for version in (0..SAVE_VERSION).chain([SAVE_VERSION + 1]) {
+            save["version"] = version.into();
+            fs::write(&path, save.to_string()).unwrap();
+            let err = SaveFile::read(&path).err().unwrap().to_string();
+            assert!(
+                err.contains(&format!(
+                    "has version {}, expected {}",
+                    version, SAVE_VERSION
+                )),
+                "{}",
+                err
+            );
+        }
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,166 @@
+use ratatui::style::{Color, Modifier, Style};
+
+pub const THEME_NAMES: [&str; 6] = [
+    "default",
+    "light",
+    "high-contrast",
+    "amber",
+    "green",
+    "none",
+];
+
+/// The colours of the screen, by what they show rather than by hue.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub text: Color,
+    /// Hints and prompts that should stay out of the way.
+    pub hint: Color,
+    /// Section headings and the title.
+    pub accent: Color,
+    /// The royal titles of the splash screen.
+    pub regal: Color,
+    pub grain: Color,
+    pub land: Color,
+    pub people: Color,
+    /// The price of land, and the rats that raise it.
+    pub price: Color,
+    /// Good news: a harvest, newcomers.
+    pub good: Color,
+    /// Deaths, rejections and the threat of impeachment.
+    pub danger: Color,
+    pub warning: Color,
+    /// The answer being typed.
+    pub input: Color,
+    pub button: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            text: Color::White,
+            hint: Color::DarkGray,
+            accent: Color::LightRed,
+            regal: Color::Magenta,
+            grain: Color::Yellow,
+            land: Color::Green,
+            people: Color::Cyan,
+            price: Color::Magenta,
+            good: Color::Green,
+            danger: Color::Red,
+            warning: Color::Yellow,
+            input: Color::Green,
+            button: Style::default().fg(Color::Black).bg(Color::Yellow),
+        }
+    }
+}
+
+impl Theme {
+    /// For terminals with a light background, where white text and yellow
+    /// figures vanish.
+    pub fn light() -> Self {
+        Self {
+            text: Color::Black,
+            hint: Color::DarkGray,
+            accent: Color::Red,
+            regal: Color::Magenta,
+            grain: Color::Rgb(150, 95, 0),
+            land: Color::Rgb(0, 120, 0),
+            people: Color::Blue,
+            price: Color::Magenta,
+            good: Color::Rgb(0, 120, 0),
+            danger: Color::Red,
+            warning: Color::Rgb(170, 80, 0),
+            input: Color::Blue,
+            button: Style::default().fg(Color::White).bg(Color::Blue),
+        }
+    }
+
+    /// Bright colours only, with hints as readable as the text.
+    pub fn high_contrast() -> Self {
+        Self {
+            text: Color::White,
+            hint: Color::Gray,
+            accent: Color::LightRed,
+            regal: Color::LightMagenta,
+            grain: Color::LightYellow,
+            land: Color::LightGreen,
+            people: Color::LightCyan,
+            price: Color::LightMagenta,
+            good: Color::LightGreen,
+            danger: Color::LightRed,
+            warning: Color::LightYellow,
+            input: Color::LightGreen,
+            button: Style::default()
+                .fg(Color::Black)
+                .bg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        }
+    }
+
+    /// A single phosphor, as on a monochrome CRT: everything in `bright`
+    /// and hints in `dim`.
+    pub fn monochrome(bright: Color, dim: Color) -> Self {
+        Self {
+            text: bright,
+            hint: dim,
+            accent: bright,
+            regal: bright,
+            grain: bright,
+            land: bright,
+            people: bright,
+            price: bright,
+            good: bright,
+            danger: bright,
+            warning: bright,
+            input: bright,
+            button: Style::default().fg(Color::Black).bg(bright),
+        }
+    }
+
+    /// The terminal's own colours, for `NO_COLOR`.
+    pub fn none() -> Self {
+        Self {
+            button: Style::default().add_modifier(Modifier::REVERSED),
+            ..Self::monochrome(Color::Reset, Color::Reset)
+        }
+    }
+
+    /// Themes by name, as accepted by `--theme`.
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default()),
+            "light" => Some(Self::light()),
+            "high-contrast" => Some(Self::high_contrast()),
+            "amber" => Some(Self::monochrome(
+                Color::Rgb(255, 176, 0),
+                Color::Rgb(160, 105, 0),
+            )),
+            "green" => Some(Self::monochrome(
+                Color::Rgb(51, 255, 51),
+                Color::Rgb(30, 150, 30),
+            )),
+            "none" => Some(Self::none()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_listed_theme_has_a_name() {
+        for name in THEME_NAMES {
+            assert!(Theme::named(name).is_some(), "{}", name);
+        }
+        assert_eq!(Theme::named("none"), Some(Theme::none()));
+    }
+
+    #[test]
+    fn unknown_names_are_not_themes() {
+        for name in ["", "sepia", "Light", "high contrast"] {
+            assert_eq!(Theme::named(name), None, "{}", name);
+        }
+    }
+}
@@ -1,10 +1,17 @@
-use crate::app::App;
-use crate::game::GamePhase;
+use crate::app::{App, Preset};
+use crate::help::help_sections;
+use crate::input::{evaluate, LineEditor};
+use crate::planner::FIELD_NAMES;
+use crate::theme::Theme;
+use hammurabi::game::{
+    classic, describe_price_change, GameAction, GamePhase, GameState, Rejection, Rules, YearEvent,
+};
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
+    symbols::Marker,
     text::{Line, Span},
-    widgets::{Paragraph, Widget},
+    widgets::{Axis, Block, Borders, Chart, Clear, Dataset, GraphType, Paragraph, Widget, Wrap},
     Frame,
 };
 use tui_big_text::{BigText, PixelSize};
@@ -12,6 +19,63 @@ use tui_big_text::{BigText, PixelSize};
 const MAX_WIDTH: u16 = 80;
 const MAX_HEIGHT: u16 = 24;
 
+/// Terminals at least this large get a pane for each part of the screen.
+const WIDE_WIDTH: u16 = 120;
+const WIDE_HEIGHT: u16 = 36;
+const SIDE_PANEL_WIDTH: u16 = 44;
+
+/// The panes of the layout for large terminals.
+struct Panes {
+    status: Rect,
+    annals: Rect,
+    decision: Rect,
+    fortunes: Rect,
+}
+
+/// Splits a large terminal into the status and annals down the left and the
+/// decision above the charts on the right, or `None` when it is too small
+/// and the single column is used.
+fn wide_panes(size: Rect, rules: &Rules) -> Option<Panes> {
+    if size.width < WIDE_WIDTH || size.height < WIDE_HEIGHT {
+        return None;
+    }
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(SIDE_PANEL_WIDTH), Constraint::Min(0)])
+        .split(size);
+    // The treasury, grain price, taxes, unrest and buildings take rows of
+    // their own
+    let mut status_height = 11;
+    if rules.uses_silver() {
+        status_height += 2;
+    }
+    if rules.taxation.enabled {
+        status_height += 2;
+    }
+    if rules.granaries.enabled {
+        status_height += 1;
+    }
+    if rules.construction.enabled {
+        status_height += 4;
+    }
+    let left = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(status_height), Constraint::Min(0)])
+        .split(columns[0]);
+    let right = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(MAX_HEIGHT), Constraint::Percentage(40)])
+        .split(columns[1]);
+
+    Some(Panes {
+        status: left[0],
+        annals: left[1],
+        decision: right[0].inner(Margin::new(1, 0)),
+        fortunes: right[1],
+    })
+}
+
 fn centered_rect(max_width: u16, max_height: u16, r: Rect) -> Rect {
     let width = r.width.min(max_width);
     let height = r.height.min(max_height);
@@ -44,18 +108,44 @@ pub fn draw(frame: &mut Frame, app: &App) {
     // Create centered area with max 80x24 dimensions
     let area = centered_rect(MAX_WIDTH, MAX_HEIGHT, frame.area());
 
+    draw_screen(frame, area, app);
+    if app.show_help {
+        draw_help(frame, area, app);
+    }
+}
+
+fn draw_screen(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     // Handle splash screen
     if matches!(app.game.current_phase, GamePhase::Splash) {
-        draw_splash(frame, area);
+        draw_splash(frame, area, &app.theme);
         return;
     }
 
     // Handle instructions screen separately
     if matches!(app.game.current_phase, GamePhase::Instructions) {
-        draw_instructions(frame, area);
+        draw_instructions(frame, area, &app.theme, &app.game.rules);
         return;
     }
 
+    if app.show_chronicle {
+        draw_chronicle(frame, area, app);
+        return;
+    }
+
+    if app.show_charts {
+        draw_charts(frame, area, app);
+        return;
+    }
+
+    let panes = wide_panes(frame.area(), &app.game.rules);
+    if let Some(panes) = &panes {
+        draw_status(frame, panes.status, app);
+        draw_annals(frame, panes.annals, app);
+        draw_fortunes(frame, panes.fortunes, app);
+    }
+    let area = panes.as_ref().map_or(area, |panes| panes.decision);
+
     let mut content = Vec::new();
 
     // Title and year
@@ -64,110 +154,425 @@ pub fn draw(frame: &mut Frame, app: &App) {
             "HAMMURABI: I BEG TO REPORT TO THEE, IN YEAR {} OF THY REIGN",
             app.game.year
         ),
-        Style::default()
-            .fg(Color::White)
-            .add_modifier(Modifier::BOLD),
+        Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
     )]));
     content.push(Line::from(""));
 
-    // Status bar
-    content.push(Line::from(vec![
-        Span::raw("BUSHELS: "),
-        Span::styled(
-            format!("{}", app.game.grain),
-            Style::default().fg(Color::Yellow),
-        ),
-        Span::raw("  ACRES: "),
-        Span::styled(
-            format!("{}", app.game.land),
-            Style::default().fg(Color::Green),
-        ),
-        Span::raw("  PEOPLE: "),
-        Span::styled(
-            format!("{}", app.game.population),
-            Style::default().fg(Color::Cyan),
-        ),
-        Span::raw("  YEAR: "),
-        Span::styled(
-            format!("{}", app.game.year),
-            Style::default().fg(Color::White),
-        ),
-    ]));
-    content.push(Line::from(""));
-
-    // Event messages or game content
-    if matches!(
-        app.game.current_phase,
-        GamePhase::YearEnd | GamePhase::GameOver
-    ) {
-        // Show events
-        for msg in &app.event_messages {
-            let color = if msg.contains("died") || msg.contains("starved") || msg.contains("plague")
-            {
-                Color::Red
-            } else if msg.contains("came to") || msg.contains("Harvest") {
-                Color::Green
-            } else if msg.contains("Rats") {
-                Color::Magenta
-            } else {
-                Color::White
-            };
-
-            content.push(Line::from(vec![
-                Span::raw("  "),
-                Span::styled(msg.clone(), Style::default().fg(color)),
-            ]));
+    // Status bar, which has a panel of its own on wide terminals
+    if panes.is_none() {
+        let mut status = vec![
+            Span::raw("BUSHELS: "),
+            Span::styled(
+                format!("{}", app.game.grain),
+                Style::default().fg(theme.grain),
+            ),
+            Span::raw("  ACRES: "),
+            Span::styled(
+                format!("{}", app.game.land),
+                Style::default().fg(theme.land),
+            ),
+        ];
+        if app.game.rules.uses_silver() {
+            status.push(Span::raw("  SILVER: "));
+            status.push(Span::styled(
+                format!("{}", app.game.silver),
+                Style::default().fg(theme.price),
+            ));
         }
+        if app.game.rules.taxation.enabled {
+            status.push(Span::raw("  UNREST: "));
+            status.push(Span::styled(
+                format!("{}", app.game.unrest),
+                Style::default().fg(theme.warning),
+            ));
+        }
+        status.extend([
+            Span::raw("  PEOPLE: "),
+            Span::styled(
+                format!("{}", app.game.population),
+                Style::default().fg(theme.people),
+            ),
+            Span::raw("  YEAR: "),
+            Span::styled(
+                format!("{}", app.game.year),
+                Style::default().fg(theme.text),
+            ),
+        ]);
+        content.push(Line::from(status));
         content.push(Line::from(""));
     }
 
+    // Event messages or final report
+    match app.game.current_phase {
+        GamePhase::YearEnd => {
+            for msg in app.event_messages.iter().skip(app.log_scroll as usize) {
+                content.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled(
+                        msg.text.clone(),
+                        Style::default().fg(msg
+                            .event
+                            .as_ref()
+                            .map_or(theme.text, |event| event_color(theme, event))),
+                    ),
+                ]));
+            }
+            content.push(Line::from(""));
+        }
+        GamePhase::GameOver => {
+            for line in app.final_report.iter().skip(app.log_scroll as usize) {
+                content.push(Line::from(vec![Span::raw("  "), Span::raw(line.clone())]));
+            }
+            content.push(Line::from(""));
+        }
+        _ => {}
+    }
+
     // Input section
-    render_input_section(&app.game, &app.input_buffer, &mut content);
+    if app.planner_active() {
+        render_planner(app, &mut content);
+    } else {
+        render_input_section(
+            &app.theme,
+            &app.game,
+            app.trading_grain(),
+            app.selling_land(),
+            &app.input,
+            &mut content,
+        );
+    }
+
+    if app.game.current_phase == GamePhase::Feeding {
+        let warning = evaluate(app.input.as_str(), &app.keywords())
+            .ok()
+            .and_then(|bushels| u32::try_from(bushels).ok())
+            .and_then(|bushels| app.feeding_warning(bushels));
+        if let Some(warning) = warning {
+            content.push(Line::from(Span::styled(
+                warning,
+                Style::default().fg(theme.warning),
+            )));
+        }
+    }
+
+    if !app.draft.is_empty() && app.playback.is_none() && app.input.is_empty() {
+        content.push(Line::from(vec![Span::styled(
+            "BACKSPACE ON AN EMPTY ANSWER TAKES BACK THY LAST DECREE",
+            Style::default().fg(theme.hint),
+        )]));
+    }
+
+    if let Some(playback) = &app.playback {
+        content.push(Line::from(""));
+        content.push(Line::from(vec![Span::styled(
+            format!(
+                "REPLAY: ENTER TO STEP, A FOR AUTO-PLAY ({})",
+                if playback.auto { "ON" } else { "OFF" }
+            ),
+            Style::default().fg(theme.hint),
+        )]));
+    }
 
     // Error message
     if !app.message.is_empty() {
         content.push(Line::from(""));
         content.push(Line::from(vec![Span::styled(
             format!("! {}", app.message),
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(theme.danger)
+                .add_modifier(Modifier::BOLD),
         )]));
     }
 
     let paragraph = Paragraph::new(content)
-        .style(Style::default().fg(Color::White))
+        .style(Style::default().fg(theme.text))
         .alignment(Alignment::Left)
-        .wrap(ratatui::widgets::Wrap { trim: true });
+        .wrap(Wrap { trim: true });
+
+    let buttons = preset_buttons(app, area);
+    if buttons.is_empty() {
+        paragraph.render(area, frame.buffer_mut());
+        return;
+    }
+
+    let text_area = Rect {
+        height: area.height.saturating_sub(1),
+        ..area
+    };
+    paragraph.render(text_area, frame.buffer_mut());
+    for (preset, button) in buttons {
+        Paragraph::new(preset_label(preset))
+            .style(app.theme.button)
+            .render(button, frame.buffer_mut());
+    }
+}
+
+fn preset_label(preset: Preset) -> &'static str {
+    match preset {
+        Preset::MaxBuy => " MAX BUY ",
+        Preset::KeepLand => " KEEP LAND ",
+        Preset::MaxPlant => " MAX PLANT ",
+        Preset::FullRation => " FULL RATION ",
+        Preset::Decree => " DECREE ",
+    }
+}
+
+/// Where each clickable preset sits: side by side on the bottom row.
+fn preset_buttons(app: &App, area: Rect) -> Vec<(Preset, Rect)> {
+    let mut x = area.x;
+    let mut buttons = Vec::new();
+    for preset in app.presets() {
+        let width = preset_label(preset).len() as u16;
+        if area.height == 0 || x + width > area.right() {
+            break;
+        }
+        buttons.push((preset, Rect::new(x, area.bottom() - 1, width, 1)));
+        x += width + 1;
+    }
+    buttons
+}
 
-    paragraph.render(area, frame.buffer_mut());
+/// The preset under a click at `column`, `row` of a terminal of `size`.
+pub fn preset_at(app: &App, size: Rect, column: u16, row: u16) -> Option<Preset> {
+    let area = wide_panes(size, &app.game.rules).map_or_else(
+        || centered_rect(MAX_WIDTH, MAX_HEIGHT, size),
+        |panes| panes.decision,
+    );
+    preset_buttons(app, area)
+        .into_iter()
+        .find(|(_, button)| button.contains(ratatui::layout::Position::new(column, row)))
+        .map(|(preset, _)| preset)
+}
+
+fn event_color(theme: &Theme, event: &YearEvent) -> Color {
+    match event {
+        YearEvent::Starvation { .. } | YearEvent::Plague { .. } => theme.danger,
+        YearEvent::Harvest { .. } => theme.good,
+        YearEvent::Immigration { count: 0 } => theme.text,
+        YearEvent::Immigration { .. } => theme.good,
+        YearEvent::Rats { .. } | YearEvent::Spoilage { .. } => theme.price,
+        YearEvent::Completed { .. } => theme.good,
+        YearEvent::Raid { .. } => theme.danger,
+        YearEvent::Upkeep { ruined: 0, .. } => theme.price,
+        YearEvent::Upkeep { .. } => theme.warning,
+        YearEvent::Unrest { change, .. } if *change > 0 => theme.warning,
+        YearEvent::Unrest { .. } => theme.text,
+    }
+}
+
+fn land_price_line(theme: &Theme, game: &GameState) -> Line<'static> {
+    let mut spans = vec![
+        Span::raw("LAND IS TRADING AT "),
+        Span::styled(
+            format!("{}", game.land_price),
+            Style::default()
+                .fg(theme.grain)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!(" {} PER ACRE.", game.rules.land_currency())),
+    ];
+    if let Some(change) = game.price_change() {
+        spans.push(Span::styled(
+            format!(" ({})", describe_price_change(change)),
+            Style::default().fg(theme.price),
+        ));
+    }
+    Line::from(spans)
+}
+
+fn grain_price_line(theme: &Theme, game: &GameState) -> Line<'static> {
+    Line::from(vec![
+        Span::raw("GRAIN IS TRADING AT "),
+        Span::styled(
+            format!("{}", game.grain_price),
+            Style::default()
+                .fg(theme.grain)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!(
+            " SHEKELS PER HUNDRED BUSHELS. THY TREASURY: {} SHEKELS.",
+            game.silver
+        )),
+    ])
+}
+
+fn render_planner<'a>(app: &'a App, content: &mut Vec<Line<'a>>) {
+    let theme = &app.theme;
+    let Some(planner) = &app.planner else {
+        return;
+    };
+    let game = &app.game;
+
+    content.push(land_price_line(theme, game));
+    content.push(Line::from(""));
+
+    let mut fields = Vec::new();
+    for (i, (name, value)) in FIELD_NAMES.iter().zip(&planner.fields).enumerate() {
+        let style = if i == planner.focus {
+            Style::default()
+                .fg(theme.input)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let cursor = if i == planner.focus { "_" } else { "" };
+        fields.push(Span::raw(format!("{}: ", name)));
+        fields.push(Span::styled(
+            format!("{:<8}", format!("{}{}", value, cursor)),
+            style,
+        ));
+        fields.push(Span::raw("  "));
+    }
+    content.push(Line::from(fields));
+    content.push(Line::from(""));
+
+    let Some(decisions) = planner.decisions() else {
+        content.push(Line::from(Span::styled(
+            "THY DECREES MUST BE WHOLE NUMBERS",
+            Style::default().fg(theme.danger),
+        )));
+        return;
+    };
+    let projection = game.project(&decisions);
+
+    content.push(Line::from(format!(
+        "GRAIN LEFT: {}   ACRES: {}   SOWABLE ACRES: {}",
+        projection.grain_remaining, projection.land, projection.max_plantable_acres
+    )));
+    content.push(Line::from(vec![
+        Span::raw("HARVEST: "),
+        Span::styled(
+            format!(
+                "{} TO {} BUSHELS",
+                projection.harvest_min, projection.harvest_max
+            ),
+            Style::default().fg(theme.good),
+        ),
+    ]));
+    let shortfall_color = if projection.feeding_shortfall > 0 {
+        theme.warning
+    } else {
+        theme.text
+    };
+    content.push(Line::from(Span::styled(
+        format!(
+            "FEEDING SHORTFALL: {} BUSHELS",
+            projection.feeding_shortfall
+        ),
+        Style::default().fg(shortfall_color),
+    )));
+    let mut starvation = vec![Span::styled(
+        format!("STARVATION: {} SOULS", projection.starvation),
+        Style::default().fg(if projection.starvation > 0 {
+            theme.danger
+        } else {
+            theme.text
+        }),
+    )];
+    if projection.impeachment {
+        starvation.push(Span::styled(
+            " - THOU WOULDST BE CAST FROM THY THRONE!",
+            Style::default()
+                .fg(theme.danger)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    content.push(Line::from(starvation));
+
+    if let Some((action, reason)) = &projection.rejection {
+        content.push(Line::from(Span::styled(
+            rejection_text(action, reason),
+            Style::default().fg(theme.danger),
+        )));
+    }
+
+    content.push(Line::from(""));
+    content.push(Line::from(Span::styled(
+        "LEFT/RIGHT CHOOSE, ENTER DECREES THE YEAR, F4 ASKS ONE QUESTION AT A TIME",
+        Style::default().fg(theme.hint),
+    )));
+}
+
+fn rejection_text(action: &GameAction, reason: &Rejection) -> String {
+    let decree = match action {
+        GameAction::BuyLand(acres) => format!("BUY {} ACRES", acres),
+        GameAction::SellLand(acres) => format!("SELL {} ACRES", acres),
+        GameAction::PlantAcres(acres) => format!("SOW {} ACRES", acres),
+        GameAction::FeedPopulation(bushels) => format!("GIVE {} BUSHELS", bushels),
+        GameAction::BuyGrain(bushels) => format!("BUY {} BUSHELS", bushels),
+        GameAction::SellGrain(bushels) => format!("SELL {} BUSHELS", bushels),
+        GameAction::SetTaxRate(rate) => format!("TAX {}%", rate),
+        GameAction::Build(works) => format!("BUILD {}", works),
+    };
+    let reason = match reason {
+        Rejection::InsufficientGrain => "THY STORES ARE TOO SMALL",
+        Rejection::InsufficientLand => "THOU HAST NOT THE LAND",
+        Rejection::InsufficientPopulation => "THOU HAST NOT THE PEOPLE",
+        Rejection::InsufficientSilver => "THY TREASURY IS TOO SMALL",
+        Rejection::MarketClosed => "NO MERCHANT WILL TRADE IN GRAIN",
+        Rejection::TaxTooHigh => "THY PEOPLE CANNOT BEAR IT",
+        Rejection::CannotBuild => "THY REALM HATH NO BUILDERS",
+    };
+    format!("THOU CANST NOT {}: {}", decree, reason)
 }
 
 fn render_input_section<'a>(
-    game: &crate::game::GameState,
-    input_buffer: &'a str,
+    theme: &Theme,
+    game: &hammurabi::game::GameState,
+    trading_grain: bool,
+    selling_land: bool,
+    input: &'a LineEditor,
     content: &mut Vec<Line<'a>>,
 ) {
-    use crate::game::GamePhase;
-
     match game.current_phase {
         GamePhase::Splash | GamePhase::Instructions => {
             // These shouldn't be reached as they are handled separately
         }
-        GamePhase::LandTransaction => {
-            let max_buy = if game.land_price > 0 {
-                game.grain / game.land_price
-            } else {
-                0
-            };
-            content.push(Line::from(vec![
-                Span::raw("LAND IS TRADING AT "),
-                Span::styled(
-                    format!("{}", game.land_price),
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD),
+        GamePhase::Taxation => {
+            let taxation = &game.rules.taxation;
+            content.push(Line::from(""));
+            content.push(Line::from("WHAT TAX WILT THOU LEVY, IN PERCENT?"));
+            content.push(Line::from(vec![Span::styled(
+                format!(
+                    "(THY LIMIT: {}, LAST YEAR: {}, UNREST: {} OF {})",
+                    taxation.max_rate, game.tax_rate, game.unrest, taxation.revolt_unrest
                 ),
-                Span::raw(" BUSHELS PER ACRE."),
-            ]));
+                Style::default().fg(theme.hint),
+            )]));
+        }
+        GamePhase::LandTransaction if trading_grain => {
+            content.push(grain_price_line(theme, game));
+            content.push(Line::from(""));
+            content.push(Line::from("HOW MANY BUSHELS WILT THOU BUY WITH SILVER?"));
+            content.push(Line::from(vec![Span::styled(
+                format!(
+                    "(NEGATIVE TO SELL, THOU CANST BUY: {}, THY STORES: {})",
+                    game.grain_for_silver(game.silver),
+                    game.grain
+                ),
+                Style::default().fg(theme.hint),
+            )]));
+        }
+        GamePhase::LandTransaction if selling_land => {
+            content.push(Line::from(""));
+            content.push(Line::from(format!("{}?", classic::SELL_PROMPT)));
+            content.push(Line::from(vec![Span::styled(
+                format!("(THY HOLDINGS: {})", game.land),
+                Style::default().fg(theme.hint),
+            )]));
+        }
+        GamePhase::LandTransaction if game.rules.is_classic() => {
+            content.push(land_price_line(theme, game));
+            content.push(Line::from(""));
+            content.push(Line::from(format!("{}?", classic::BUY_PROMPT)));
+            content.push(Line::from(vec![Span::styled(
+                format!("(THOU CANST BUY: {})", game.affordable_acres()),
+                Style::default().fg(theme.hint),
+            )]));
+        }
+        GamePhase::LandTransaction => {
+            let max_buy = game.affordable_acres();
+            content.push(land_price_line(theme, game));
             content.push(Line::from(""));
             content.push(Line::from("HOW MANY ACRES DOST THOU WISH TO ACQUIRE?"));
             content.push(Line::from(vec![Span::styled(
@@ -175,21 +580,47 @@ fn render_input_section<'a>(
                     "(NEGATIVE TO SELL, THOU CANST BUY: {}, THY HOLDINGS: {})",
                     max_buy, game.land
                 ),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.hint),
+            )]));
+        }
+        GamePhase::Construction => {
+            content.push(Line::from(""));
+            content.push(Line::from("WHAT SHALL THY PEOPLE BUILD?"));
+            for building in game.rules.buildable() {
+                let project = game.rules.project(building);
+                content.push(Line::from(vec![Span::styled(
+                    format!(
+                        "({}: {} BUSHELS, {} WORKERS, {} {}, UPKEEP {}; THOU HAST {})",
+                        building.name(1),
+                        project.grain_cost,
+                        project.workers,
+                        project.years,
+                        if project.years == 1 { "YEAR" } else { "YEARS" },
+                        project.upkeep,
+                        game.built(building)
+                    ),
+                    Style::default().fg(theme.hint),
+                )]));
+            }
+            content.push(Line::from(vec![Span::styled(
+                format!(
+                    "(UNDER WAY: {}; ANSWER AS IN 2 GRANARIES, WALL)",
+                    game.works_under_way()
+                ),
+                Style::default().fg(theme.hint),
             )]));
         }
         GamePhase::Planting => {
             let max_plant = game.max_plantable_acres();
-            let max_by_pop = game.population * 10;
-            let max_by_grain = game.grain;
-            let _max_by_land = game.land;
+            let max_by_pop = game.workable_acres();
+            let max_by_grain = game.rules.acres_sown_with(game.grain);
 
             content.push(Line::from(""));
             content.push(Line::from("HOW MANY ACRES WILT THOU PLANT WITH SEED?"));
             content.push(Line::from(vec![
                 Span::styled(
                     format!("(THY LIMIT: {} - CONSTRAINED BY ", max_plant),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme.hint),
                 ),
                 Span::styled(
                     if max_plant == max_by_pop {
@@ -199,9 +630,9 @@ fn render_input_section<'a>(
                     } else {
                         "THY LAND"
                     },
-                    Style::default().fg(Color::Yellow),
+                    Style::default().fg(theme.warning),
                 ),
-                Span::styled(")", Style::default().fg(Color::DarkGray)),
+                Span::styled(")", Style::default().fg(theme.hint)),
             ]));
         }
         GamePhase::Feeding => {
@@ -215,7 +646,7 @@ fn render_input_section<'a>(
                     "(THY PEOPLE REQUIRE: {}, THY STORES HOLD: {})",
                     need, game.grain
                 ),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.hint),
             )]));
         }
         GamePhase::YearEnd => {
@@ -223,16 +654,16 @@ fn render_input_section<'a>(
             content.push(Line::from(vec![Span::styled(
                 "PRESS ENTER TO CONTINUE THY REIGN...",
                 Style::default()
-                    .fg(Color::DarkGray)
+                    .fg(theme.hint)
                     .add_modifier(Modifier::ITALIC),
             )]));
         }
         GamePhase::GameOver => {
             content.push(Line::from(""));
             content.push(Line::from(vec![Span::styled(
-                "PRESS ESC TO DEPART THIS MORTAL REALM",
+                "PRESS ENTER TO READ THY CHRONICLE, ESC TO DEPART THIS MORTAL REALM",
                 Style::default()
-                    .fg(Color::DarkGray)
+                    .fg(theme.hint)
                     .add_modifier(Modifier::ITALIC),
             )]));
         }
@@ -243,20 +674,346 @@ fn render_input_section<'a>(
         game.current_phase,
         GamePhase::Splash | GamePhase::Instructions | GamePhase::YearEnd | GamePhase::GameOver
     ) {
+        let (before, after) = input.as_str().split_at(input.cursor());
+        let mut after = after.chars();
+        let under_cursor = after.next().map_or("_".to_string(), String::from);
         content.push(Line::from(vec![
             Span::raw("? "),
-            Span::styled(input_buffer, Style::default().fg(Color::Green)),
+            Span::styled(before, Style::default().fg(theme.input)),
             Span::styled(
-                "_",
+                under_cursor,
                 Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::RAPID_BLINK),
+                    .fg(theme.input)
+                    .add_modifier(Modifier::RAPID_BLINK | Modifier::UNDERLINED),
             ),
+            Span::styled(after.as_str(), Style::default().fg(theme.input)),
         ]));
     }
 }
 
-fn draw_instructions(frame: &mut Frame, area: Rect) {
+fn draw_charts(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let game = &app.game;
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Title
+            Constraint::Min(0),    // Top charts
+            Constraint::Min(0),    // Bottom charts
+            Constraint::Length(1), // Prompt
+        ])
+        .split(area);
+
+    let title = Paragraph::new("THE FORTUNES OF THY KINGDOM")
+        .style(Style::default().fg(theme.text).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center);
+    title.render(rows[0], frame.buffer_mut());
+
+    let [grain, land, population, price] = trends(game);
+
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[1]);
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[2]);
+
+    draw_trend(frame, top[0], theme, "BUSHELS", &grain, theme.grain);
+    draw_trend(frame, top[1], theme, "ACRES", &land, theme.land);
+    draw_trend(frame, bottom[0], theme, "PEOPLE", &population, theme.people);
+    draw_trend(frame, bottom[1], theme, "LAND PRICE", &price, theme.price);
+
+    let prompt = Paragraph::new("PRESS TAB TO RETURN")
+        .style(
+            Style::default()
+                .fg(theme.hint)
+                .add_modifier(Modifier::ITALIC),
+        )
+        .alignment(Alignment::Center);
+    prompt.render(rows[3], frame.buffer_mut());
+}
+
+/// Grain, acres, people and the price of land: each year's opening value,
+/// followed by where the kingdom stands now.
+fn trends(game: &GameState) -> [Vec<u32>; 4] {
+    let history = &game.history;
+    let grain = history
+        .iter()
+        .map(|r| r.start_grain)
+        .chain([game.grain])
+        .collect();
+    let land = history
+        .iter()
+        .map(|r| r.start_land)
+        .chain([game.land])
+        .collect();
+    let population = history
+        .iter()
+        .map(|r| r.start_population)
+        .chain([game.population])
+        .collect();
+    [grain, land, population, game.price_history()]
+}
+
+fn draw_status(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let game = &app.game;
+    let term = if game.unlimited_mode {
+        format!("{}", game.year)
+    } else {
+        format!("{} OF {}", game.year, game.rules.years)
+    };
+    let row = |label: &'static str, value: String, color: Color| {
+        Line::from(vec![
+            Span::raw(format!("{:<18}", label)),
+            Span::styled(value, Style::default().fg(color)),
+        ])
+    };
+
+    let mut lines = vec![
+        row("YEAR", term, theme.text),
+        row("BUSHELS", game.grain.to_string(), theme.grain),
+        row("ACRES", game.land.to_string(), theme.land),
+        row("PEOPLE", game.population.to_string(), theme.people),
+        row("LAND PRICE", game.land_price.to_string(), theme.price),
+    ];
+    if game.rules.uses_silver() {
+        lines.push(row("SILVER", game.silver.to_string(), theme.price));
+        lines.push(row(
+            "GRAIN PRICE",
+            format!("{} PER 100", game.grain_price),
+            theme.price,
+        ));
+    }
+    if game.rules.taxation.enabled {
+        lines.push(row("TAX RATE", format!("{}%", game.tax_rate), theme.price));
+        lines.push(row(
+            "UNREST",
+            format!("{} OF {}", game.unrest, game.rules.taxation.revolt_unrest),
+            theme.warning,
+        ));
+    }
+    if game.rules.granaries.enabled {
+        lines.push(row(
+            "GRANARIES",
+            format!("{} (HOLD {})", game.granaries, game.granary_capacity()),
+            theme.grain,
+        ));
+    }
+    if game.rules.construction.enabled {
+        lines.push(row("CANALS", game.canals.to_string(), theme.land));
+        lines.push(row("WALLS", game.walls.to_string(), theme.land));
+        lines.push(row("TEMPLES", game.temples.to_string(), theme.land));
+        lines.push(row("BUILDERS", game.builders().to_string(), theme.people));
+    }
+    lines.extend([
+        row(
+            "GRAIN TO FEED ALL",
+            game.grain_needed_for_feeding().to_string(),
+            theme.grain,
+        ),
+        row(
+            "ACRES TILLABLE",
+            game.workable_acres().to_string(),
+            theme.land,
+        ),
+        row("DEATHS SO FAR", game.total_deaths.to_string(), theme.danger),
+    ]);
+
+    Paragraph::new(lines)
+        .block(Block::bordered().title(" THY KINGDOM "))
+        .render(area, frame.buffer_mut());
+}
+
+/// Every year resolved so far, the latest first.
+fn draw_annals(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let mut lines = Vec::new();
+    for record in app.game.history.iter().rev() {
+        let deaths = record.deaths_starvation + record.deaths_plague;
+        lines.push(Line::from(Span::styled(
+            format!(
+                "YEAR {}: {} BUSHELS AN ACRE, RATS ATE {}",
+                record.year, record.harvest_yield, record.grain_eaten_by_rats
+            ),
+            Style::default().fg(theme.text),
+        )));
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  {} CAME, {} STARVED, {} TO PLAGUE",
+                record.new_citizens, record.deaths_starvation, record.deaths_plague
+            ),
+            Style::default().fg(if deaths > 0 { theme.danger } else { theme.hint }),
+        )));
+    }
+
+    Paragraph::new(lines)
+        .block(Block::bordered().title(" THE ANNALS "))
+        .render(area, frame.buffer_mut());
+}
+
+fn draw_fortunes(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let [grain, land, population, price] = trends(&app.game);
+    let charts = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Ratio(1, 4); 4])
+        .split(area);
+
+    draw_trend(frame, charts[0], theme, "BUSHELS", &grain, theme.grain);
+    draw_trend(frame, charts[1], theme, "ACRES", &land, theme.land);
+    draw_trend(frame, charts[2], theme, "PEOPLE", &population, theme.people);
+    draw_trend(frame, charts[3], theme, "LAND PRICE", &price, theme.price);
+}
+
+fn draw_trend(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    title: &str,
+    values: &[u32],
+    color: Color,
+) {
+    let points: Vec<(f64, f64)> = values
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| ((i + 1) as f64, value as f64))
+        .collect();
+
+    let max_x = (values.len() as f64).max(2.0);
+    let max_y = (values.iter().copied().max().unwrap_or(0) as f64 * 1.1).max(1.0);
+    let latest = values.last().copied().unwrap_or(0);
+
+    let dataset = Dataset::default()
+        .marker(Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(color))
+        .data(&points);
+
+    let chart = Chart::new(vec![dataset])
+        .block(Block::bordered().title(format!(" {}: {} ", title, latest)))
+        .x_axis(
+            Axis::default()
+                .bounds([1.0, max_x])
+                .style(Style::default().fg(theme.hint))
+                .labels(vec!["1".to_string(), format!("{}", max_x as u32)]),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds([0.0, max_y])
+                .style(Style::default().fg(theme.hint))
+                .labels(vec!["0".to_string(), format!("{}", max_y as u32)]),
+        );
+
+    chart.render(area, frame.buffer_mut());
+}
+
+fn draw_chronicle(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Title
+            Constraint::Length(1), // Column headers
+            Constraint::Min(0),    // Years
+            Constraint::Length(1), // Prompt
+        ])
+        .split(area);
+
+    let title = Paragraph::new("THE CHRONICLE OF THY REIGN")
+        .style(Style::default().fg(theme.text).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center);
+    title.render(chunks[0], frame.buffer_mut());
+
+    let header = Paragraph::new(format!(
+        "{:>3} {:>3} {:>11} {:>9} {:>7} {:>4} {:>5} {:>1} {:>4} {:>3} {:>3} {:>3}",
+        "YR", "PRC", "GRAIN", "ACRES", "PEOPLE", "SOWN", "FED", "Y", "RATS", "IMM", "STV", "PLG"
+    ))
+    .style(Style::default().fg(theme.hint));
+    header.render(chunks[1], frame.buffer_mut());
+
+    let rows: Vec<Line> = app
+        .game
+        .history
+        .iter()
+        .map(|record| {
+            let deaths = record.deaths_starvation + record.deaths_plague;
+            let style = if deaths > 0 {
+                Style::default().fg(theme.danger)
+            } else {
+                Style::default().fg(theme.text)
+            };
+
+            Line::from(Span::styled(
+                format!(
+                    "{:>3} {:>3} {:>5}>{:<5} {:>4}>{:<4} {:>3}>{:<3} {:>4} {:>5} {:>1} {:>4} {:>3} {:>3} {:>3}",
+                    record.year,
+                    record.land_price,
+                    record.start_grain,
+                    record.end_grain,
+                    record.start_land,
+                    record.end_land,
+                    record.start_population,
+                    record.end_population,
+                    record.acres_planted,
+                    record.grain_fed,
+                    record.harvest_yield,
+                    record.grain_eaten_by_rats,
+                    record.new_citizens,
+                    record.deaths_starvation,
+                    record.deaths_plague,
+                ),
+                style,
+            ))
+        })
+        .collect();
+
+    let years = Paragraph::new(rows).scroll((app.chronicle_scroll, 0));
+    years.render(chunks[2], frame.buffer_mut());
+
+    let prompt = Paragraph::new("UP/DOWN OR WHEEL TO SCROLL, ENTER OR ESC TO DEPART")
+        .style(
+            Style::default()
+                .fg(theme.hint)
+                .add_modifier(Modifier::ITALIC),
+        )
+        .alignment(Alignment::Center);
+    prompt.render(chunks[3], frame.buffer_mut());
+}
+
+/// The help overlay, drawn over whatever screen is showing.
+fn draw_help(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let mut lines = Vec::new();
+    for section in help_sections(app) {
+        lines.push(Line::from(Span::styled(
+            section.title,
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )));
+        lines.extend(section.lines.into_iter().map(Line::from));
+        lines.push(Line::from(""));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" COUNSEL OF THE SCRIBES ")
+        .title_bottom(" UP/DOWN TO SCROLL, ? OR ESC TO RETURN ")
+        .style(Style::default().fg(theme.text));
+    let help = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((app.help_scroll, 0));
+
+    Clear.render(area, frame.buffer_mut());
+    help.render(area, frame.buffer_mut());
+}
+
+fn draw_instructions(frame: &mut Frame, area: Rect, theme: &Theme, rules: &Rules) {
     // Use responsive padding based on terminal size
     let padding = if frame.area().width >= 80 && frame.area().height >= 24 {
         1
@@ -278,41 +1035,49 @@ fn draw_instructions(frame: &mut Frame, area: Rect) {
 
     // Title
     let title = Paragraph::new("HAMURABI INSTRUCTIONS")
-        .style(
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
-        )
+        .style(Style::default().fg(theme.text).add_modifier(Modifier::BOLD))
         .alignment(Alignment::Center);
     title.render(chunks[0], frame.buffer_mut());
 
     // Instructions text
-    let instructions = vec![
+    let mut instructions = vec![
         Line::from(""),
         Line::from(vec![
             Span::styled(
                 "I. ",
                 Style::default()
-                    .fg(Color::LightRed)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
-                "HOW MUCH LAND TO BUY OR SELL (LAND COSTS BETWEEN 17 AND 26 BUSHELS OF",
-                Style::default().fg(Color::White),
+                format!(
+                    "HOW MUCH LAND TO BUY OR SELL (LAND COSTS BETWEEN {} AND {} {} OF",
+                    rules.land_price_min,
+                    rules.land_price_max,
+                    rules.land_currency()
+                ),
+                Style::default().fg(theme.text),
             ),
         ]),
-        Line::from("   GRAIN)."),
+        Line::from(if rules.uses_silver() {
+            "   SILVER, AND GRAIN MAY BE BOUGHT OR SOLD FOR SILVER)."
+        } else {
+            "   GRAIN)."
+        }),
         Line::from(""),
         Line::from(vec![
             Span::styled(
                 "II. ",
                 Style::default()
-                    .fg(Color::LightRed)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
-                "HOW MANY BUSHELS TO FEED YOUR PEOPLE (20 PER PERSON PER YEAR",
-                Style::default().fg(Color::White),
+                format!(
+                    "HOW MANY BUSHELS TO FEED YOUR PEOPLE ({} PER PERSON PER YEAR",
+                    rules.bushels_per_person
+                ),
+                Style::default().fg(theme.text),
             ),
         ]),
         Line::from("    REQUIRED)."),
@@ -321,35 +1086,94 @@ fn draw_instructions(frame: &mut Frame, area: Rect) {
             Span::styled(
                 "III. ",
                 Style::default()
-                    .fg(Color::LightRed)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
-                "HOW MANY ACRES OF LAND TO PLANT SEED IN (REQUIRES 1 BUSHEL +",
-                Style::default().fg(Color::White),
+                if rules.acres_per_seed_bushel == 1 {
+                    "HOW MANY ACRES OF LAND TO PLANT SEED IN (REQUIRES 1 BUSHEL +".to_string()
+                } else {
+                    format!(
+                        "HOW MANY ACRES OF LAND TO PLANT SEED IN (REQUIRES 1/{} BUSHEL +",
+                        rules.acres_per_seed_bushel
+                    )
+                },
+                Style::default().fg(theme.text),
             ),
         ]),
-        Line::from("     1/10TH A PERSON TO TILL PER YEAR)."),
+        Line::from(format!(
+            "     1/{}TH A PERSON TO TILL PER YEAR).",
+            rules.acres_per_worker
+        )),
     ];
+    // Optional decisions are numbered on from the three every reign has
+    let mut numerals = ["IV. ", "V. "].into_iter();
+    if rules.taxation.enabled {
+        instructions.extend([
+            Line::from(""),
+            Line::from(vec![
+                Span::styled(
+                    numerals.next().unwrap_or_default(),
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!(
+                        "WHAT TAX TO LEVY FIRST EACH YEAR (UP TO {}%; HEAVY TAXES DETER",
+                        rules.taxation.max_rate
+                    ),
+                    Style::default().fg(theme.text),
+                ),
+            ]),
+            Line::from("    NEWCOMERS AND STIR UNREST)."),
+        ]);
+    }
+    if rules.builds() {
+        let buildings: Vec<&str> = rules
+            .buildable()
+            .into_iter()
+            .map(|building| building.name(2))
+            .collect();
+        instructions.extend([
+            Line::from(""),
+            Line::from(vec![
+                Span::styled(
+                    numerals.next().unwrap_or_default(),
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!(
+                        "WHAT TO BUILD ({}), PAID FOR IN GRAIN,",
+                        buildings.join(", ")
+                    ),
+                    Style::default().fg(theme.text),
+                ),
+            ]),
+            Line::from("    IN WORKERS KEPT FROM THE FIELDS AND IN UPKEEP."),
+        ]);
+    }
 
     let instructions_paragraph = Paragraph::new(instructions)
-        .style(Style::default().fg(Color::White))
+        .style(Style::default().fg(theme.text))
         .alignment(Alignment::Left)
-        .wrap(ratatui::widgets::Wrap { trim: true });
+        .wrap(Wrap { trim: true });
     instructions_paragraph.render(chunks[1], frame.buffer_mut());
 
     // Press enter prompt
     let prompt = Paragraph::new("PRESS ENTER TO START...")
         .style(
             Style::default()
-                .fg(Color::DarkGray)
+                .fg(theme.hint)
                 .add_modifier(Modifier::ITALIC),
         )
         .alignment(Alignment::Center);
     prompt.render(chunks[3], frame.buffer_mut());
 }
 
-fn draw_splash(frame: &mut Frame, area: Rect) {
+fn draw_splash(frame: &mut Frame, area: Rect, theme: &Theme) {
     // Calculate total content height
     let title_height = if frame.area().width < 80 || frame.area().height < 24 {
         4 // Quadrant size needs less height
@@ -391,7 +1215,7 @@ fn draw_splash(frame: &mut Frame, area: Rect) {
     // Top decoration
     let decoration_line = "═══════════════════════════════════════════════════════════════";
     let decoration_paragraph = Paragraph::new(decoration_line)
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(theme.hint))
         .alignment(Alignment::Center);
     decoration_paragraph.render(chunks[0], frame.buffer_mut());
 
@@ -400,9 +1224,9 @@ fn draw_splash(frame: &mut Frame, area: Rect) {
         "ASSUME THE THRONE OF ANCIENT BABYLON\nRULE WITH WISDOM AND JUSTICE AS THE MIGHTY...";
 
     let top_paragraph = Paragraph::new(top_text)
-        .style(Style::default().fg(Color::White))
+        .style(Style::default().fg(theme.text))
         .alignment(Alignment::Center)
-        .wrap(ratatui::widgets::Wrap { trim: true });
+        .wrap(Wrap { trim: true });
     top_paragraph.render(chunks[1], frame.buffer_mut());
 
     // Create the big text widget - use smaller size for smaller terminals
@@ -416,7 +1240,7 @@ fn draw_splash(frame: &mut Frame, area: Rect) {
         .pixel_size(pixel_size)
         .style(
             Style::default()
-                .fg(Color::LightRed)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         )
         .lines(vec!["HAMMURABI".into()])
@@ -430,14 +1254,14 @@ fn draw_splash(frame: &mut Frame, area: Rect) {
         Line::from(vec![Span::styled(
             "KING OF ANCIENT BABYLONIA",
             Style::default()
-                .fg(Color::Magenta)
+                .fg(theme.regal)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Guide thy kingdom through ten years of tribulation",
             Style::default()
-                .fg(Color::DarkGray)
+                .fg(theme.hint)
                 .add_modifier(Modifier::ITALIC),
         )]),
     ];
@@ -447,7 +1271,7 @@ fn draw_splash(frame: &mut Frame, area: Rect) {
 
     // Bottom decoration
     let bottom_decoration_paragraph = Paragraph::new(decoration_line)
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(theme.hint))
         .alignment(Alignment::Center);
     bottom_decoration_paragraph.render(chunks[4], frame.buffer_mut());
 
@@ -458,7 +1282,7 @@ fn draw_splash(frame: &mut Frame, area: Rect) {
             Span::styled(
                 "<ENTER>",
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(theme.warning)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(" TO ASCEND THE THRONE"),
@@ -467,7 +1291,9 @@ fn draw_splash(frame: &mut Frame, area: Rect) {
             Span::raw("PRESS "),
             Span::styled(
                 "<ESC>",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(theme.danger)
+                    .add_modifier(Modifier::BOLD),
             ),
             Span::raw(" TO FLEE THY DESTINY"),
         ]),
@@ -476,3 +1302,70 @@ fn draw_splash(frame: &mut Frame, area: Rect) {
     let instructions_paragraph = Paragraph::new(instructions).alignment(Alignment::Center);
     instructions_paragraph.render(chunks[6], frame.buffer_mut());
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The smallest terminal the game fits on.
+    const SMALL: Rect = Rect {
+        x: 0,
+        y: 0,
+        width: MAX_WIDTH,
+        height: MAX_HEIGHT,
+    };
+
+    fn at(phase: GamePhase) -> App {
+        let mut app = App::new(Some(1), false, Rules::default());
+        app.game.current_phase = phase;
+        app
+    }
+
+    #[test]
+    fn each_preset_answers_a_click_on_it() {
+        let app = at(GamePhase::LandTransaction);
+        let bottom = MAX_HEIGHT - 1;
+        let mut x = 0;
+        for preset in [Preset::MaxBuy, Preset::KeepLand, Preset::Decree] {
+            let width = preset_label(preset).len() as u16;
+            assert_eq!(preset_at(&app, SMALL, x, bottom), Some(preset));
+            assert_eq!(preset_at(&app, SMALL, x + width - 1, bottom), Some(preset));
+            // The gap between buttons
+            assert_eq!(preset_at(&app, SMALL, x + width, bottom), None);
+            x += width + 1;
+        }
+    }
+
+    #[test]
+    fn a_click_off_the_presets_is_ignored() {
+        let app = at(GamePhase::LandTransaction);
+        assert_eq!(preset_at(&app, SMALL, 0, 0), None);
+        assert_eq!(preset_at(&app, SMALL, 0, MAX_HEIGHT - 2), None);
+        assert_eq!(preset_at(&app, SMALL, MAX_WIDTH - 1, MAX_HEIGHT - 1), None);
+
+        // Nothing is clickable between years
+        let app = at(GamePhase::YearEnd);
+        assert_eq!(preset_at(&app, SMALL, 0, MAX_HEIGHT - 1), None);
+    }
+
+    #[test]
+    fn panes_need_both_the_width_and_the_height() {
+        let rules = Rules::default();
+        let size = |width, height| Rect::new(0, 0, width, height);
+        assert!(wide_panes(size(WIDE_WIDTH, WIDE_HEIGHT), &rules).is_some());
+        assert!(wide_panes(size(WIDE_WIDTH - 1, WIDE_HEIGHT), &rules).is_none());
+        assert!(wide_panes(size(WIDE_WIDTH, WIDE_HEIGHT - 1), &rules).is_none());
+        assert!(wide_panes(size(WIDE_WIDTH - 1, WIDE_HEIGHT - 1), &rules).is_none());
+    }
+
+    #[test]
+    fn the_panes_split_the_terminal() {
+        let panes =
+            wide_panes(Rect::new(0, 0, WIDE_WIDTH, WIDE_HEIGHT), &Rules::default()).unwrap();
+        assert_eq!(panes.status.width, SIDE_PANEL_WIDTH);
+        assert_eq!(panes.annals.x, 0);
+        assert!(panes.decision.x > SIDE_PANEL_WIDTH);
+        assert!(panes.decision.height >= MAX_HEIGHT);
+        assert_eq!(panes.fortunes.bottom(), WIDE_HEIGHT);
+    }
+}
@@ -0,0 +1,70 @@
+//! Golden transcripts of the classic ruleset. Each file under
+//! `tests/golden` was produced by this implementation, following the 1973
+//! listing's text and arithmetic, for the given answers and seed; none was
+//! captured from a run of the original program, so they guard against
+//! regressions rather than prove fidelity. The tests in `src/plain.rs` play
+//! the same answers through plain mode; these check the engine and the
+//! listing's text on their own.
+
+use hammurabi::game::{classic, GameAction, GamePhase, GameState, Rules};
+
+include!("golden/reigns.rs");
+
+fn transcript(seed: u64, answers: &[[u32; 4]]) -> String {
+    let mut state = GameState::with_rules(Some(seed), false, Rules::classic());
+    state.current_phase = GamePhase::LandTransaction;
+
+    let mut lines = classic::opening();
+    lines.extend(classic::year_report(&state));
+
+    for &[buy, sell, feed, plant] in answers {
+        lines.push(classic::land_price(state.land_price));
+        lines.push(format!("{}? {}", classic::BUY_PROMPT, buy));
+        if buy == 0 {
+            lines.push(format!("{}? {}", classic::SELL_PROMPT, sell));
+        }
+        lines.push(String::new());
+        lines.push(format!("{}? {}", classic::FEED_PROMPT, feed));
+        lines.push(String::new());
+        lines.push(format!("{}? {}", classic::PLANT_PROMPT, plant));
+
+        let land = if buy > 0 {
+            GameAction::BuyLand(buy)
+        } else {
+            GameAction::SellLand(sell)
+        };
+        for action in [
+            land,
+            GameAction::FeedPopulation(feed),
+            GameAction::PlantAcres(plant),
+        ] {
+            assert_eq!(
+                state.execute_action(action),
+                hammurabi::ActionResult::Success
+            );
+        }
+
+        let report = state.resolve_year();
+        if state.was_impeached() {
+            lines.extend(classic::impeachment(state.deaths_starvation));
+            break;
+        }
+
+        lines.extend(classic::year_report(&state));
+        if report.game_over {
+            lines.extend(classic::final_report(&mut state));
+            break;
+        }
+        state.advance_year();
+    }
+
+    lines.extend(classic::farewell());
+    lines.join("\n") + "\n"
+}
+
+#[test]
+fn golden_reigns() {
+    for reign in GOLDEN_REIGNS {
+        assert_golden(reign, &transcript(reign.seed, reign.years));
+    }
+}
@@ -0,0 +1,154 @@
+//! Canals, walls and temples, built and kept over several years.
+
+use hammurabi::game::{
+    Building, Game, GameAction, Rejection, Rules, Works, YearError, YearEvent, YearReport,
+};
+
+/// Construction without rats, raids or plague, so each test lets in only
+/// the chance it is about.
+fn building_rules() -> Rules {
+    let mut rules = Rules::default();
+    rules.construction.enabled = true;
+    rules.construction.raid_chance = 0;
+    rules.rats_chance = 0;
+    rules.plague_chance = 0;
+    rules
+}
+
+/// A kingdom of `seed` under `rules` with stores to spare.
+fn kingdom(seed: u64, rules: Rules) -> Game {
+    let mut state = Game::with_rules(Some(seed), false, rules).into_state();
+    state.grain = 20_000;
+    Game::from_state(state)
+}
+
+/// Plays a year that begins `works`, sows `plant` acres and feeds everyone.
+fn build_year(game: &mut Game, works: Works, plant: u32) -> Result<YearReport, YearError> {
+    let feed = game.state().grain_needed_for_feeding();
+    game.play_actions(&[
+        GameAction::Build(works),
+        GameAction::PlantAcres(plant),
+        GameAction::FeedPopulation(feed),
+    ])
+}
+
+#[test]
+fn a_temple_stands_after_three_years() {
+    let mut game = kingdom(1, building_rules());
+    let temple = Works {
+        temples: 1,
+        ..Works::default()
+    };
+    for works in [temple, Works::default()] {
+        let report = build_year(&mut game, works, 500).unwrap();
+        assert!(!report
+            .events
+            .iter()
+            .any(|event| matches!(event, YearEvent::Completed { .. })));
+        assert_eq!(game.state().temples, 0);
+        assert_eq!(game.state().works_under_way(), temple);
+        assert_eq!(game.state().builders(), 8);
+    }
+
+    let report = build_year(&mut game, Works::default(), 500).unwrap();
+    assert!(report.events.contains(&YearEvent::Completed {
+        building: Building::Temple,
+        count: 1
+    }));
+    assert_eq!(game.state().temples, 1);
+    assert_eq!(game.state().builders(), 0);
+}
+
+#[test]
+fn builders_leave_fields_untilled() {
+    let mut game = kingdom(1, building_rules());
+    let walls = Works {
+        walls: 2,
+        ..Works::default()
+    };
+    build_year(&mut game, walls, 500).unwrap();
+
+    let state = game.state();
+    let workable = (state.population - 20) * state.rules.acres_per_worker;
+    assert_eq!(state.workable_acres(), workable);
+    match build_year(&mut game, Works::default(), workable + 1) {
+        Err(YearError::Rejected { reason, .. }) => {
+            assert_eq!(reason, Rejection::InsufficientPopulation)
+        }
+        other => panic!("{:?}", other),
+    }
+    build_year(&mut game, Works::default(), workable).unwrap();
+    assert_eq!(game.state().walls, 2);
+}
+
+#[test]
+fn walls_cut_raid_losses() {
+    let raided = |walls| {
+        let mut rules = building_rules();
+        rules.construction.raid_chance = 100;
+        rules.construction.raid_loss_min = 40;
+        rules.construction.raid_loss_max = 40;
+        let mut game = kingdom(1, rules);
+        let mut state = game.into_state();
+        state.walls = walls;
+        game = Game::from_state(state);
+        let report = build_year(&mut game, Works::default(), 500).unwrap();
+        report
+            .events
+            .iter()
+            .find_map(|event| match event {
+                YearEvent::Raid { percent, .. } => Some(*percent),
+                _ => None,
+            })
+            .unwrap()
+    };
+    assert_eq!(raided(0), 40);
+    assert_eq!(raided(2), 20);
+    // Walls never save more than three quarters
+    assert_eq!(raided(4), 10);
+}
+
+#[test]
+fn temples_keep_the_plague_away() {
+    let plagues = |temples| {
+        let mut rules = building_rules();
+        rules.plague_chance = 8;
+        let mut count = 0;
+        for seed in 0..50 {
+            let mut state = kingdom(seed, rules.clone()).into_state();
+            state.temples = temples;
+            let mut game = Game::from_state(state);
+            for _ in 0..3 {
+                let report = build_year(&mut game, Works::default(), 0).unwrap();
+                count += report
+                    .events
+                    .iter()
+                    .filter(|event| matches!(event, YearEvent::Plague { .. }))
+                    .count();
+            }
+        }
+        count
+    };
+    assert!(plagues(0) > 0);
+    // Two temples lower the chance by eight points
+    assert_eq!(plagues(2), 0);
+}
+
+#[test]
+fn unpaid_upkeep_ruins_the_costliest_buildings() {
+    let mut state = Game::with_rules(Some(1), false, building_rules()).into_state();
+    state.temples = 2;
+    state.walls = 1;
+    state.grain = state.grain_needed_for_feeding() + 40;
+    let mut game = Game::from_state(state);
+
+    // Seventy bushels are due and forty are left once the people are fed
+    let report = build_year(&mut game, Works::default(), 0).unwrap();
+    assert!(report.events.contains(&YearEvent::Upkeep {
+        paid: 40,
+        ruined: 1
+    }));
+    assert_eq!(game.state().temples, 1);
+    assert_eq!(game.state().walls, 1);
+    assert_eq!(game.state().grain, 0);
+}
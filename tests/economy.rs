@@ -0,0 +1,128 @@
+//! The silver economy: grain bought and sold for shekels, and its price.
+
+use hammurabi::game::{Game, GamePhase, GameState, Rejection, Rules, YearDecisions, YearError};
+
+fn silver_rules() -> Rules {
+    let mut rules = Rules::default();
+    rules.economy.enabled = true;
+    rules
+}
+
+/// The first year of a silver reign, with grain at `price` shekels.
+fn treasury(price: u32, silver: u32) -> GameState {
+    let mut state = GameState::with_rules(Some(1), false, silver_rules());
+    state.current_phase = GamePhase::LandTransaction;
+    state.grain_price = price;
+    state.silver = silver;
+    state
+}
+
+#[test]
+fn purchases_round_up_and_sales_round_down() {
+    let state = treasury(75, 0);
+    assert_eq!(state.grain_cost(1), 1);
+    assert_eq!(state.grain_value(1), 0);
+    assert_eq!(state.grain_cost(100), 75);
+    assert_eq!(state.grain_value(100), 75);
+    assert_eq!(state.grain_cost(98), 74);
+    assert_eq!(state.grain_value(99), 74);
+    assert_eq!(state.grain_for_silver(74), 98);
+}
+
+#[test]
+fn the_treasury_buys_as_much_as_it_can_pay_for() {
+    for price in [60, 75, 99, 101, 140] {
+        for silver in [0, 1, 74, 999, 1000] {
+            let state = treasury(price, silver);
+            let bushels = state.grain_for_silver(silver);
+            assert!(state.grain_cost(bushels) <= silver as u64);
+            assert!(state.grain_cost(bushels + 1) > silver as u64);
+
+            let most = YearDecisions {
+                grain: bushels as i32,
+                ..YearDecisions::default()
+            };
+            let mut game = Game::from_state(state.clone());
+            assert!(game.play_year(&most).is_ok(), "{} at {}", silver, price);
+
+            let more = YearDecisions {
+                grain: bushels as i32 + 1,
+                ..YearDecisions::default()
+            };
+            let mut game = Game::from_state(state);
+            match game.play_year(&more) {
+                Err(YearError::Rejected { reason, .. }) => {
+                    assert_eq!(reason, Rejection::InsufficientSilver)
+                }
+                other => panic!("{:?}", other),
+            }
+        }
+    }
+}
+
+/// Next year's grain price after a quiet year in which `bought` bushels
+/// were bought and `sold` sold, without the market's chance.
+fn next_price(bought: u32, sold: u32) -> u32 {
+    let mut rules = silver_rules();
+    rules.economy.volatility = 0.0;
+    let mut state = GameState::with_rules(Some(1), false, rules);
+    state.current_phase = GamePhase::YearEnd;
+    state.grain_price = 100;
+    state.harvest_yield = 3;
+    state.grain_bought = bought;
+    state.grain_sold = sold;
+    state.advance_year();
+    state.grain_price
+}
+
+#[test]
+fn trading_moves_the_grain_price() {
+    assert_eq!(next_price(0, 0), 100);
+    assert_eq!(next_price(2000, 0), 110);
+    assert_eq!(next_price(0, 2000), 90);
+}
+
+#[test]
+fn grain_cannot_be_traded_without_silver() {
+    for grain in [100, -100] {
+        let mut game = Game::with_rules(Some(1), false, Rules::default());
+        let decisions = YearDecisions {
+            grain,
+            plant: 500,
+            feed: 2000,
+            ..YearDecisions::default()
+        };
+        match game.play_year(&decisions) {
+            Err(YearError::Rejected { reason, .. }) => {
+                assert_eq!(reason, Rejection::MarketClosed)
+            }
+            other => panic!("{:?}", other),
+        }
+    }
+}
+
+#[test]
+fn trades_beyond_reckoning_are_refused() {
+    let mut state = treasury(200, u32::MAX);
+    state.grain = u32::MAX;
+    let sale = YearDecisions {
+        grain: -1,
+        ..YearDecisions::default()
+    };
+    match Game::from_state(state.clone()).play_year(&sale) {
+        Err(YearError::Rejected { reason, .. }) => assert_eq!(reason, Rejection::InsufficientGrain),
+        other => panic!("{:?}", other),
+    }
+
+    state.silver = 2;
+    let purchase = YearDecisions {
+        grain: 1,
+        ..YearDecisions::default()
+    };
+    match Game::from_state(state).play_year(&purchase) {
+        Err(YearError::Rejected { reason, .. }) => {
+            assert_eq!(reason, Rejection::InsufficientSilver)
+        }
+        other => panic!("{:?}", other),
+    }
+}
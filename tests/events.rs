@@ -0,0 +1,56 @@
+//! The events of a year, checked against what they did to the kingdom.
+
+use hammurabi::game::{GameAction, GameState, YearEvent};
+
+/// The kingdom of `seed` after a first year sowing `plant` acres and
+/// handing out `feed` bushels.
+fn resolved(seed: u64, plant: u32, feed: u32) -> (GameState, Vec<YearEvent>) {
+    let mut state = GameState::new(Some(seed), false);
+    state.execute_action(GameAction::PlantAcres(plant));
+    state.execute_action(GameAction::FeedPopulation(feed));
+    let report = state.resolve_year();
+    (state, report.events)
+}
+
+#[test]
+fn each_event_reports_what_befell_the_kingdom() {
+    for seed in 0..100 {
+        let (state, events) = resolved(seed, 700, 2000);
+        assert_eq!(
+            events[0],
+            YearEvent::Harvest {
+                yield_per_acre: state.harvest_yield,
+                total: state.grain_harvested,
+            }
+        );
+        assert_eq!(state.grain_harvested, 700 * state.harvest_yield);
+
+        let rats = events
+            .iter()
+            .find_map(|event| match event {
+                YearEvent::Rats { eaten, .. } => Some(*eaten),
+                _ => None,
+            })
+            .unwrap_or(0);
+        assert_eq!(rats, state.grain_eaten_by_rats, "seed {}", seed);
+
+        assert!(events.contains(&YearEvent::Immigration {
+            count: state.new_citizens
+        }));
+
+        let plague = events.contains(&YearEvent::Plague {
+            deaths: state.deaths_plague,
+        });
+        assert_eq!(plague, state.deaths_plague > 0, "seed {}", seed);
+        assert!(!events
+            .iter()
+            .any(|event| matches!(event, YearEvent::Starvation { .. })));
+    }
+}
+
+#[test]
+fn starvation_is_reported_first_and_keeps_newcomers_away() {
+    let (_, events) = resolved(1, 700, 1000);
+    assert_eq!(events[0], YearEvent::Starvation { deaths: 50 });
+    assert!(events.contains(&YearEvent::Immigration { count: 0 }));
+}
@@ -0,0 +1,76 @@
+// The answers behind the golden transcripts in this directory, included by
+// tests/classic.rs and by the plain mode tests in src/plain.rs so both play
+// the same reigns.
+
+/// A classic reign: the transcript it prints, the seed it was played with
+/// and its answers a year at a time: acres to buy, to sell, bushels to feed
+/// and acres to plant. The sell prompt is only answered when nothing is
+/// bought.
+struct GoldenReign {
+    file: &'static str,
+    seed: u64,
+    years: &'static [[u32; 4]],
+}
+
+const GOLDEN_REIGNS: &[GoldenReign] = &[
+    GoldenReign {
+        file: "classic_steady.txt",
+        seed: 1,
+        years: &[
+            [0, 0, 2000, 999],
+            [0, 0, 2060, 1000],
+            [0, 0, 2200, 1000],
+            [0, 0, 1160, 579],
+            [0, 0, 780, 389],
+            [0, 0, 1140, 569],
+            [0, 0, 1460, 729],
+            [0, 0, 800, 399],
+            [0, 0, 920, 459],
+            [0, 0, 700, 349],
+        ],
+    },
+    GoldenReign {
+        file: "classic_trading.txt",
+        seed: 3,
+        years: &[
+            [2, 0, 2000, 999],
+            [0, 18, 2140, 984],
+            [0, 37, 2280, 947],
+            [0, 0, 1240, 619],
+            [0, 0, 1620, 809],
+            [0, 33, 1760, 879],
+            [0, 2, 1820, 909],
+            [199, 0, 2080, 1039],
+            [167, 0, 2140, 1069],
+            [0, 24, 2460, 1229],
+        ],
+    },
+    GoldenReign {
+        file: "classic_stingy.txt",
+        seed: 1,
+        years: &[
+            [0, 0, 1800, 999],
+            [0, 0, 1674, 929],
+            [0, 0, 1638, 909],
+            [0, 0, 792, 439],
+            [0, 0, 594, 329],
+            [0, 0, 900, 499],
+            [0, 0, 1152, 639],
+            [0, 0, 576, 319],
+            [0, 0, 630, 349],
+            [0, 0, 576, 319],
+        ],
+    },
+    GoldenReign {
+        file: "classic_impeached.txt",
+        seed: 7,
+        years: &[[0, 0, 2000, 999], [0, 0, 2140, 318], [0, 0, 318, 0]],
+    },
+];
+
+/// Checks `actual` against the transcript the reign is expected to print.
+fn assert_golden(reign: &GoldenReign, actual: &str) {
+    let path = format!("{}/tests/golden/{}", env!("CARGO_MANIFEST_DIR"), reign.file);
+    let expected = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(actual, expected, "transcript differs from {}", path);
+}
@@ -0,0 +1,134 @@
+//! Granaries, alone and alongside the rest of construction.
+
+use hammurabi::game::{
+    ActionResult, Building, Game, GameAction, GameState, Rules, Works, YearDecisions, YearEvent,
+};
+
+/// Granaries without rats, so only spoilage touches the stores.
+fn granary_rules() -> Rules {
+    let mut rules = Rules::default();
+    rules.granaries.enabled = true;
+    rules.rats_chance = 0;
+    rules
+}
+
+/// The grain left to rot after feeding the people from `grain` bushels in
+/// a year with nothing planted.
+fn spoiled(rules: Rules, grain: u32) -> (u32, Vec<YearEvent>) {
+    let mut state = Game::with_rules(Some(1), false, rules).into_state();
+    state.grain = grain;
+    let mut game = Game::from_state(state);
+    let report = game
+        .play_year(&YearDecisions {
+            feed: 2000,
+            ..YearDecisions::default()
+        })
+        .unwrap();
+    (
+        game.state().history.last().unwrap().grain_spoiled,
+        report.events,
+    )
+}
+
+#[test]
+fn granaries_alone_are_kept_up() {
+    let mut rules = Rules::default();
+    rules.granaries.enabled = true;
+    rules.granaries.upkeep = 10;
+    let mut game = Game::with_rules(Some(1), false, rules);
+    let report = game
+        .play_year(&YearDecisions {
+            plant: 500,
+            feed: 2000,
+            ..YearDecisions::default()
+        })
+        .unwrap();
+    assert!(
+        report.events.contains(&YearEvent::Upkeep {
+            paid: 20,
+            ruined: 0
+        }),
+        "{:?}",
+        report.events
+    );
+    assert_eq!(game.state().history.last().unwrap().upkeep_paid, 20);
+}
+
+#[test]
+fn grain_beyond_the_granaries_spoils() {
+    // Two granaries hold 3000 bushels and half the rest rots
+    let (rotted, events) = spoiled(granary_rules(), 7000);
+    assert_eq!(rotted, 1000);
+    assert!(events.contains(&YearEvent::Spoilage { spoiled: 1000 }));
+
+    let (rotted, events) = spoiled(granary_rules(), 4000);
+    assert_eq!(rotted, 0);
+    assert!(!events
+        .iter()
+        .any(|event| matches!(event, YearEvent::Spoilage { .. })));
+}
+
+#[test]
+fn rat_protection_is_capped() {
+    let mut state = GameState::with_rules(Some(1), false, granary_rules());
+    for (granaries, protection) in [(0, 0), (3, 30), (6, 60), (10, 60)] {
+        state.granaries = granaries;
+        assert_eq!(state.rat_protection(), protection);
+    }
+
+    let mut rules = granary_rules();
+    rules.rats_chance = 100;
+    rules.rats_damage_min = 50;
+    rules.rats_damage_max = 50;
+    let mut state = Game::with_rules(Some(1), false, rules).into_state();
+    state.granaries = 10;
+    let report = Game::from_state(state)
+        .play_year(&YearDecisions {
+            feed: 2000,
+            ..YearDecisions::default()
+        })
+        .unwrap();
+    assert!(report
+        .events
+        .iter()
+        .any(|event| matches!(event, YearEvent::Rats { percent: 20, .. })));
+}
+
+#[test]
+fn a_granary_costs_grain_and_labour() {
+    let mut state = Game::with_rules(Some(1), false, granary_rules()).into_state();
+    let grain = state.grain;
+    let acres = state.workable_acres();
+    let works = Works {
+        granaries: 2,
+        ..Works::default()
+    };
+    assert_eq!(
+        state.execute_action(GameAction::Build(works)),
+        ActionResult::Success
+    );
+    assert_eq!(state.grain, grain - 600);
+    assert_eq!(state.builders(), 10);
+    assert_eq!(state.workable_acres(), acres - 100);
+}
+
+#[test]
+fn a_granary_under_construction_rules_stands_the_same_year() {
+    let mut rules = granary_rules();
+    rules.construction.enabled = true;
+    let mut game = Game::with_rules(Some(1), false, rules);
+    let report = game
+        .play_year(&YearDecisions {
+            granaries: 1,
+            plant: 500,
+            feed: 2000,
+            ..YearDecisions::default()
+        })
+        .unwrap();
+    assert!(report.events.contains(&YearEvent::Completed {
+        building: Building::Granary,
+        count: 1
+    }));
+    assert_eq!(game.state().granaries, 3);
+    assert_eq!(game.state().builders(), 0);
+}
@@ -0,0 +1,96 @@
+//! The year ledger, checked to add up over whole reigns.
+
+use hammurabi::game::{Game, GameState, YearDecisions};
+
+/// Trades a few acres each year, feeds everyone the stores allow and sows
+/// what is left.
+fn decide(state: &GameState) -> YearDecisions {
+    let land: i32 = if state.year.is_multiple_of(2) {
+        -5
+    } else if state.grain >= 5 * state.land_price + state.grain_needed_for_feeding() {
+        5
+    } else {
+        0
+    };
+    let land_after = state.land.saturating_add_signed(land);
+    let grain = state.grain as i64 - land as i64 * state.land_price as i64;
+    let grain = grain.max(0) as u32;
+    let feed = state.grain_needed_for_feeding().min(grain);
+    YearDecisions {
+        land,
+        plant: (grain - feed).min(land_after).min(state.population * 10),
+        feed,
+        ..YearDecisions::default()
+    }
+}
+
+fn reign(seed: u64) -> GameState {
+    let mut game = Game::new(Some(seed), false);
+    while !game.is_over() {
+        let decisions = decide(game.state());
+        game.play_year(&decisions).unwrap();
+    }
+    game.into_state()
+}
+
+#[test]
+fn every_row_adds_up() {
+    for seed in 0..20 {
+        let state = reign(seed);
+        assert!(!state.history.is_empty());
+        for row in &state.history {
+            assert_eq!(
+                row.end_population,
+                row.start_population - row.deaths_starvation - row.deaths_plague + row.new_citizens,
+                "seed {} year {}",
+                seed,
+                row.year
+            );
+
+            let traded = row.end_land as i64 - row.start_land as i64;
+            let harvest = row.acres_planted * row.harvest_yield;
+            let end_grain = row.start_grain as i64
+                - traded * row.land_price as i64
+                - row.acres_planted as i64
+                - row.grain_fed as i64
+                + harvest as i64
+                - row.grain_eaten_by_rats as i64;
+            assert_eq!(
+                row.end_grain as i64, end_grain,
+                "seed {} year {}",
+                seed, row.year
+            );
+        }
+    }
+}
+
+#[test]
+fn each_year_starts_where_the_last_ended() {
+    for seed in 0..20 {
+        let state = reign(seed);
+        let first = &state.history[0];
+        assert_eq!(
+            (
+                first.year,
+                first.start_grain,
+                first.start_land,
+                first.start_population
+            ),
+            (1, 2800, 1000, 100)
+        );
+        for pair in state.history.windows(2) {
+            let (last, next) = (&pair[0], &pair[1]);
+            assert_eq!(next.year, last.year + 1);
+            assert_eq!(next.start_grain, last.end_grain);
+            assert_eq!(next.start_land, last.end_land);
+            assert_eq!(next.start_population, last.end_population);
+        }
+
+        let deaths: u32 = state
+            .history
+            .iter()
+            .map(|row| row.deaths_starvation + row.deaths_plague)
+            .sum();
+        assert_eq!(deaths, state.total_deaths);
+    }
+}
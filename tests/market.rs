@@ -0,0 +1,125 @@
+//! The land market of the modern ruleset, checked over seeded runs.
+
+use hammurabi::game::{Game, GamePhase, GameState, Optimal, Rules, Strategy};
+
+fn reign_prices(seed: u64) -> Vec<u32> {
+    let mut game = Game::with_rules(Some(seed), false, Rules::default());
+    let mut strategy = Optimal;
+    while !game.is_over() {
+        let decisions = strategy.decide(game.state());
+        game.play_year(&decisions).unwrap();
+    }
+    game.state().price_history()
+}
+
+/// A kingdom at the end of a quiet year: an ordinary harvest, a year's
+/// rations in store and no land traded.
+fn quiet_year(seed: u64, land_price: u32) -> GameState {
+    let mut state = GameState::with_rules(Some(seed), false, Rules::default());
+    state.current_phase = GamePhase::YearEnd;
+    state.land_price = land_price;
+    state.harvest_yield = 3;
+    state.grain = state.grain_needed_for_feeding();
+    state
+}
+
+/// Mean price of the year after `state` over a run of seeds.
+fn mean_next_price(prepare: impl Fn(&mut GameState), seeds: u64) -> f64 {
+    let total: u32 = (0..seeds)
+        .map(|seed| {
+            let mut state = quiet_year(seed, 21);
+            prepare(&mut state);
+            state.advance_year();
+            state.land_price
+        })
+        .sum();
+    total as f64 / seeds as f64
+}
+
+#[test]
+fn same_seed_same_prices() {
+    for seed in 0..20 {
+        let prices = reign_prices(seed);
+        assert_eq!(prices, reign_prices(seed), "seed {} diverged", seed);
+        assert_eq!(prices.len(), 11);
+        assert!(prices.iter().all(|price| (17..=26).contains(price)));
+    }
+}
+
+#[test]
+fn prices_remember_the_year_before() {
+    let mut moves = 0;
+    let mut steps = 0;
+    for seed in 0..50 {
+        for pair in reign_prices(seed).windows(2) {
+            moves += pair[0].abs_diff(pair[1]);
+            steps += 1;
+        }
+    }
+    // Uniform draws over 17..=26 move 3.3 a year on average
+    assert!((moves as f64 / steps as f64) < 3.0);
+}
+
+#[test]
+fn prices_revert_to_the_middle() {
+    for (start, direction) in [(26, -1.0), (17, 1.0)] {
+        let total: u32 = (0..200)
+            .map(|seed| {
+                let mut state = quiet_year(seed, start);
+                state.advance_year();
+                state.land_price
+            })
+            .sum();
+        let mean = total as f64 / 200.0;
+        assert!(
+            (mean - start as f64) * direction > 1.0,
+            "{} stayed at {}",
+            start,
+            mean
+        );
+    }
+}
+
+#[test]
+fn buying_raises_and_selling_lowers_the_price() {
+    let quiet = mean_next_price(|_| {}, 200);
+    let bought = mean_next_price(|state| state.acres_bought = 300, 200);
+    let sold = mean_next_price(|state| state.acres_sold = 300, 200);
+    assert!(bought > quiet + 3.0, "bought {} quiet {}", bought, quiet);
+    assert!(sold < quiet - 3.0, "sold {} quiet {}", sold, quiet);
+}
+
+#[test]
+fn harvest_and_stores_move_the_price() {
+    let quiet = mean_next_price(|_| {}, 200);
+    let rich = mean_next_price(|state| state.harvest_yield = 5, 200);
+    let full = mean_next_price(|state| state.grain *= 2, 200);
+    let famine = mean_next_price(
+        |state| {
+            state.harvest_yield = 1;
+            state.grain = 0;
+        },
+        200,
+    );
+    assert!(rich > quiet + 1.0);
+    assert!(full > quiet + 1.0);
+    assert!(famine < quiet - 3.0);
+}
+
+#[test]
+fn a_market_switched_off_draws_every_year() {
+    let mut rules = Rules::default();
+    rules.market.dynamic = false;
+    let total: u32 = (0..200)
+        .map(|seed| {
+            let mut state = GameState::with_rules(Some(seed), false, rules.clone());
+            state.current_phase = GamePhase::YearEnd;
+            state.land_price = 26;
+            state.acres_bought = 1000;
+            state.advance_year();
+            state.land_price
+        })
+        .sum();
+    let mean = total as f64 / 200.0;
+    assert!((20.0..23.0).contains(&mean), "mean {}", mean);
+}
@@ -0,0 +1,49 @@
+//! The year planner's projections, worked out before anything is decreed.
+
+use hammurabi::game::{GamePhase, GameState, Rules, YearDecisions};
+
+fn kingdom(rules: Rules) -> GameState {
+    let mut state = GameState::with_rules(Some(1), false, rules);
+    state.current_phase = GamePhase::LandTransaction;
+    state
+}
+
+#[test]
+fn the_harvest_spans_the_yields() {
+    let state = kingdom(Rules::default());
+    let projection = state.project(&YearDecisions {
+        plant: 500,
+        feed: 2000,
+        ..YearDecisions::default()
+    });
+    assert_eq!(projection.harvest_min, 500);
+    assert_eq!(projection.harvest_max, 2500);
+    assert_eq!(projection.rejection, None);
+}
+
+#[test]
+fn canals_raise_the_poorest_harvest() {
+    let mut rules = Rules::default();
+    rules.construction.enabled = true;
+    let mut state = kingdom(rules);
+    state.canals = 2;
+    let projection = state.project(&YearDecisions {
+        plant: 500,
+        ..YearDecisions::default()
+    });
+    assert_eq!(projection.harvest_min, 1500);
+    assert_eq!(projection.harvest_max, 2500);
+}
+
+#[test]
+fn absurd_sowing_is_rejected_without_overflowing() {
+    let state = kingdom(Rules::default());
+    for plant in [859_000_000, u32::MAX] {
+        let projection = state.project(&YearDecisions {
+            plant,
+            ..YearDecisions::default()
+        });
+        assert_eq!(projection.harvest_max, u32::MAX);
+        assert!(projection.rejection.is_some());
+    }
+}
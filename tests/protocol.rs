@@ -0,0 +1,134 @@
+//! Bots playing over `--protocol json`, driven through the binary.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use hammurabi::game::{Game, GameAction, Optimal, Rejection, Rules, Strategy, YearError};
+use serde_json::Value;
+
+/// The messages the game sends while `lines` are answered, seeded with
+/// `seed` and started with the extra `flags`.
+fn play(seed: u64, flags: &[&str], lines: &[&str]) -> Vec<Value> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_hammurabi"))
+        .args(["--protocol", "json", "--seed", &seed.to_string()])
+        .args(flags)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    for line in lines {
+        writeln!(stdin, "{}", line).unwrap();
+    }
+    drop(stdin);
+
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn a_reign_is_played_to_its_end() {
+    // The same reign played in-process gives the expected messages
+    let mut game = Game::new(Some(5), false);
+    let mut lines = Vec::new();
+    let mut expected = vec![serde_json::json!({ "type": "rules" })];
+    let state = |game: &Game| {
+        let state = game.state();
+        serde_json::json!({
+            "type": "state",
+            "year": state.year,
+            "grain": state.grain,
+            "land": state.land,
+            "population": state.population,
+            "land_price": state.land_price,
+        })
+    };
+    expected.push(state(&game));
+    while !game.is_over() {
+        let decisions = Optimal.decide(game.state());
+        lines.push(serde_json::to_string(&decisions).unwrap());
+        let report = game.play_year(&decisions).unwrap();
+        expected.push(serde_json::json!({
+            "type": "year",
+            "year": report.year,
+            "events": report.events,
+        }));
+        if !report.game_over {
+            expected.push(state(&game));
+        }
+    }
+    expected.push(serde_json::json!({ "type": "game_over", "impeached": false }));
+    assert!(expected.len() > 4);
+
+    let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+    let messages = play(5, &[], &lines);
+    assert_eq!(messages.len(), expected.len(), "{:?}", messages);
+    for (message, expected) in messages.iter().zip(&expected) {
+        for (key, value) in expected.as_object().unwrap() {
+            assert_eq!(&message[key], value, "{} in {}", key, message);
+        }
+    }
+
+    // Every event names its kind
+    let events = &messages[2]["events"];
+    assert_eq!(events[0]["type"], "harvest", "{}", events);
+}
+
+/// Sends `line` alone and returns the error it earned.
+fn refusal(flags: &[&str], line: &str) -> Value {
+    let messages = play(1, flags, &[line]);
+    let [_, _, error] = &messages[..] else {
+        panic!("{:?}", messages);
+    };
+    assert_eq!(error["type"], "error", "{}", error);
+    error.clone()
+}
+
+#[test]
+fn a_purchase_beyond_reckoning_is_refused() {
+    for line in [
+        r#"{"land":171798692,"plant":0,"feed":2000}"#,
+        r#"{"land":2000000000}"#,
+    ] {
+        assert_eq!(refusal(&[], line)["error"], "insufficient_grain");
+    }
+    assert_eq!(
+        refusal(&["--economy"], r#"{"land":2000000000}"#)["error"],
+        "insufficient_silver"
+    );
+}
+
+#[test]
+fn a_sale_beyond_reckoning_is_refused() {
+    let mut game = Game::with_rules(Some(1), false, Rules::default());
+    let mut state = game.state().clone();
+    state.land = u32::MAX;
+    game = Game::from_state(state);
+    let actions = [GameAction::SellLand(u32::MAX / 2)];
+    match game.play_actions(&actions) {
+        Err(YearError::Rejected { reason, .. }) => assert_eq!(reason, Rejection::InsufficientLand),
+        other => panic!("{:?}", other),
+    }
+    assert_eq!(
+        refusal(&[], r#"{"land":-2000000000}"#)["error"],
+        "insufficient_land"
+    );
+}
+
+#[test]
+fn works_beyond_counting_cannot_be_built() {
+    for (flags, line) in [
+        (&["--granaries"][..], r#"{"granaries":4000000000}"#),
+        (
+            &["--construction"],
+            r#"{"granaries":2000000000,"walls":2000000000}"#,
+        ),
+    ] {
+        assert_eq!(refusal(flags, line)["error"], "cannot_build");
+    }
+}
@@ -0,0 +1,102 @@
+//! Recorded reigns played back, through the library and `replay --verify`.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use hammurabi::game::{Game, Optimal, Replay, Rules, Strategy, RULES_VERSION};
+
+/// A whole reign ruled by the optimal strategy, recorded as it was played.
+fn recorded_reign(seed: u64) -> Replay {
+    let mut game = Game::with_rules(Some(seed), false, Rules::default());
+    let mut replay = Replay::new(seed, false, Rules::default());
+    let mut strategy = Optimal;
+    while !game.is_over() {
+        let actions = strategy.decide(game.state()).actions(&game.state().rules);
+        game.play_actions(&actions).unwrap();
+        replay.years.push(actions);
+    }
+    replay.score = Some(game.score());
+    replay
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("hammurabi-{}-{}.json", name, std::process::id()))
+}
+
+fn verify_with_binary(replay: &Replay, name: &str) -> std::process::Output {
+    let path = temp_path(name);
+    replay.write(&path).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_hammurabi"))
+        .arg("replay")
+        .arg(&path)
+        .arg("--verify")
+        .output()
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    output
+}
+
+#[test]
+fn a_recorded_reign_verifies() {
+    for seed in 0..10 {
+        let replay = recorded_reign(seed);
+        assert_eq!(Some(replay.verify().unwrap()), replay.score);
+    }
+}
+
+#[test]
+fn a_doctored_score_diverges() {
+    let mut replay = recorded_reign(3);
+    if let Some(score) = &mut replay.score {
+        score.total_deaths += 1;
+    }
+    let err = replay.verify().unwrap_err().to_string();
+    assert!(err.contains("replay diverged"), "{}", err);
+}
+
+#[test]
+fn an_unfinished_reign_does_not_verify() {
+    let mut replay = recorded_reign(3);
+    replay.years.pop();
+    let err = replay.verify().unwrap_err().to_string();
+    assert!(err.contains("before the reign was over"), "{}", err);
+}
+
+#[test]
+fn the_replay_subcommand_verifies_the_score() {
+    let replay = recorded_reign(5);
+    let output = verify_with_binary(&replay, "verify");
+    assert!(output.status.success());
+    let score = replay.score.unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.starts_with(&format!("Replay verified: {:?}", score.rating)),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn replays_from_other_rules_versions_are_refused() {
+    let mut replay = recorded_reign(5);
+    replay.rules_version = RULES_VERSION + 1;
+
+    let path = temp_path("rules-version");
+    replay.write(&path).unwrap();
+    let err = Replay::read(&path).unwrap_err().to_string();
+    std::fs::remove_file(&path).unwrap();
+    assert!(
+        err.contains(&format!(
+            "rules version {}, expected {}",
+            RULES_VERSION + 1,
+            RULES_VERSION
+        )),
+        "{}",
+        err
+    );
+
+    let output = verify_with_binary(&replay, "rules-version-binary");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("rules version"), "{}", stderr);
+}
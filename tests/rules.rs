@@ -0,0 +1,77 @@
+//! House rules files and the built-in rulers' reading of them.
+
+use hammurabi::game::{GamePhase, GameState, Optimal, Rules, Speculator, Strategy};
+
+fn load(name: &str, contents: &str) -> anyhow::Result<Rules> {
+    let path = std::env::temp_dir().join(format!("hammurabi-{}-{}.toml", name, std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+    let rules = Rules::from_toml_file(&path);
+    std::fs::remove_file(&path).unwrap();
+    rules
+}
+
+/// The first year of a reign under `rules`, with land at `price` and
+/// grain to spare.
+fn kingdom(rules: Rules, price: u32) -> GameState {
+    let mut state = GameState::with_rules(Some(1), false, rules);
+    state.current_phase = GamePhase::LandTransaction;
+    state.land_price = price;
+    state.grain = 5000;
+    state
+}
+
+fn price_range(min: u32, max: u32) -> Rules {
+    Rules {
+        land_price_min: min,
+        land_price_max: max,
+        ..Rules::default()
+    }
+}
+
+#[test]
+fn missing_keys_fall_back_to_the_defaults() {
+    let rules = load("partial", "years = 20\n\n[scoring]\ngood_acres = 11.0\n").unwrap();
+    assert_eq!(rules.years, 20);
+    assert_eq!(rules.scoring.good_acres, 11.0);
+    assert_eq!(
+        rules.scoring.poor_acres,
+        Rules::default().scoring.poor_acres
+    );
+    assert_eq!(rules.starting_grain, Rules::default().starting_grain);
+}
+
+#[test]
+fn misspelt_keys_are_reported() {
+    for contents in ["starting_grian = 10\n", "[scoring]\ngood_acre = 11.0\n"] {
+        let err = format!("{:#}", load("misspelt", contents).unwrap_err());
+        assert!(err.contains("unknown field"), "{}", err);
+    }
+}
+
+#[test]
+fn thresholds_follow_the_default_price_range() {
+    let rules = Rules::default();
+    assert_eq!(rules.land_price_at(1, 4), 19);
+    assert_eq!(rules.land_price_at(1, 3), 20);
+    assert_eq!(rules.land_price_at(1, 2), 22);
+    assert_eq!(rules.land_price_at(2, 3), 23);
+}
+
+#[test]
+fn the_speculator_reads_a_dearer_market() {
+    let cheap = Speculator.decide(&kingdom(price_range(40, 49), 41));
+    assert!(cheap.land > 0, "{:?}", cheap);
+
+    let mut state = kingdom(price_range(40, 49), 48);
+    state.land = 2000;
+    let dear = Speculator.decide(&state);
+    assert!(dear.land < 0, "{:?}", dear);
+}
+
+#[test]
+fn the_optimal_ruler_reads_a_cheaper_market() {
+    let mut state = kingdom(price_range(10, 14), 14);
+    state.land = 2000;
+    let decisions = Optimal.decide(&state);
+    assert_eq!(decisions.land, -1000);
+}
@@ -0,0 +1,48 @@
+//! Many reigns played by a strategy, and the tally kept of them.
+
+use hammurabi::game::{simulate, GameState, Optimal, Policy, Rules, Strategy, YearDecisions};
+
+/// Rules wisely but taxes as hard as the rules allow.
+struct Tyrant(Optimal);
+
+impl Strategy for Tyrant {
+    fn name(&self) -> &str {
+        "tyrant"
+    }
+
+    fn decide(&mut self, state: &GameState) -> YearDecisions {
+        YearDecisions {
+            tax: state.rules.taxation.max_rate,
+            ..self.0.decide(state)
+        }
+    }
+}
+
+fn taxed_rules() -> Rules {
+    let mut rules = Rules::default();
+    rules.taxation.enabled = true;
+    rules
+}
+
+#[test]
+fn overthrown_reigns_have_not_survived() {
+    let stats = simulate(10, 0, &taxed_rules(), &mut Tyrant(Optimal)).unwrap();
+    assert_eq!(stats.games, 10);
+    assert_eq!(stats.survived, 0);
+}
+
+#[test]
+fn a_ruler_within_the_tolerated_rate_survives() {
+    let stats = simulate(10, 0, &taxed_rules(), &mut Optimal).unwrap();
+    assert!(stats.survived > 0, "{:?}", stats);
+}
+
+#[test]
+fn a_gluttonous_policy_feeds_what_the_stores_hold() {
+    let mut policy = Policy {
+        feed_per_person: 100_000_000,
+        plant_percent: u32::MAX,
+    };
+    let stats = simulate(3, 0, &Rules::default(), &mut policy).unwrap();
+    assert_eq!(stats.games, 3);
+}
@@ -0,0 +1,103 @@
+//! Taxes levied at the start of each year, and the unrest they stir.
+
+use hammurabi::game::{
+    Game, Optimal, PerformanceRating, Rejection, Rules, Strategy, YearDecisions, YearError,
+    YearEvent,
+};
+
+fn taxed_rules() -> Rules {
+    let mut rules = Rules::default();
+    rules.taxation.enabled = true;
+    rules
+}
+
+/// The optimal year, levying `tax` percent instead of the tolerated rate.
+fn taxed_year(game: &Game, tax: u32) -> YearDecisions {
+    YearDecisions {
+        tax,
+        ..Optimal.decide(game.state())
+    }
+}
+
+#[test]
+fn taxes_above_the_cap_are_refused() {
+    let mut game = Game::with_rules(Some(1), false, taxed_rules());
+    let cap = game.state().rules.taxation.max_rate;
+    match game.play_year(&taxed_year(&game, cap + 1)) {
+        Err(YearError::Rejected { reason, .. }) => assert_eq!(reason, Rejection::TaxTooHigh),
+        other => panic!("{:?}", other),
+    }
+    assert!(game.play_year(&taxed_year(&game, cap)).is_ok());
+}
+
+#[test]
+fn heavy_taxes_stir_the_people_year_after_year() {
+    let mut game = Game::with_rules(Some(1), false, taxed_rules());
+    let mut levels = Vec::new();
+    for _ in 0..2 {
+        game.play_year(&taxed_year(&game, 30)).unwrap();
+        levels.push(game.state().unrest);
+    }
+    // Twenty points above the tolerated rate, one unrest each
+    assert_eq!(levels, [20, 40]);
+
+    game.play_year(&taxed_year(&game, 10)).unwrap();
+    assert_eq!(game.state().unrest, 30);
+}
+
+#[test]
+fn taxes_keep_newcomers_away() {
+    let newcomers = |tax| {
+        let mut game = Game::with_rules(Some(1), false, taxed_rules());
+        let report = game.play_year(&taxed_year(&game, tax)).unwrap();
+        report
+            .events
+            .iter()
+            .find_map(|event| match event {
+                YearEvent::Immigration { count } => Some(*count),
+                _ => None,
+            })
+            .unwrap()
+    };
+    assert!(newcomers(10) > 0);
+    // Two percent of the newcomers for every point of tax
+    assert_eq!(newcomers(50), 0);
+}
+
+#[test]
+fn the_people_overthrow_a_ruler_who_taxes_too_long() {
+    let mut game = Game::with_rules(Some(1), false, taxed_rules());
+    let mut years = 0;
+    while !game.is_over() {
+        game.play_year(&taxed_year(&game, 50)).unwrap();
+        years += 1;
+    }
+    assert_eq!(years, 3);
+    assert!(game.state().was_overthrown());
+    assert!(matches!(
+        game.play_year(&taxed_year(&game, 0)),
+        Err(YearError::GameOver)
+    ));
+}
+
+#[test]
+fn unrest_lowers_the_rating() {
+    let mut game = Game::with_rules(Some(1), false, taxed_rules());
+    let mut strategy = Optimal;
+    while !game.is_over() {
+        let decisions = strategy.decide(game.state());
+        game.play_year(&decisions).unwrap();
+    }
+    let mut state = game.into_state();
+    state.land = state.population * 20;
+    let calm = state.final_score();
+    assert_eq!(calm.mean_unrest, 0.0);
+    assert_eq!(calm.rating, PerformanceRating::Excellent);
+
+    for record in &mut state.history {
+        record.unrest = 50;
+    }
+    let restless = state.final_score();
+    assert_eq!(restless.mean_unrest, 50.0);
+    assert_eq!(restless.rating, PerformanceRating::Good);
+}